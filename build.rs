@@ -0,0 +1,64 @@
+//! Links every solution in `src/bin` into the main binary when the `registry` feature is
+//! enabled, see `src/template/registry.rs`.
+use std::{env, fs, path::PathBuf};
+
+/// Returns `true` for solution file names like `2023-05.rs`.
+fn is_solution(file_name: &str) -> bool {
+    let bytes = file_name.as_bytes();
+    file_name.len() == "2023-05.rs".len()
+        && file_name.ends_with(".rs")
+        && bytes[4] == b'-'
+        && bytes[..4]
+            .iter()
+            .chain(&bytes[5..7])
+            .all(u8::is_ascii_digit)
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+
+    // with `dhat-heap`, every solution declares a global allocator, which can only exist once.
+    let is_enabled = env::var_os("CARGO_FEATURE_REGISTRY").is_some()
+        && env::var_os("CARGO_FEATURE_DHAT_HEAP").is_none();
+
+    let mut names: Vec<String> = if is_enabled {
+        fs::read_dir(manifest_dir.join("src/bin"))
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .map(|entry| entry.file_name().to_string_lossy().to_string())
+                    .filter(|name| is_solution(name))
+                    .collect()
+            })
+            .unwrap_or_default()
+    } else {
+        vec![]
+    };
+    names.sort();
+
+    let mut code = String::new();
+    let mut solutions = vec![];
+
+    for name in &names {
+        let module = format!(
+            "solution_{}",
+            name.trim_end_matches(".rs").replace('-', "_")
+        );
+        let path = manifest_dir.join("src/bin").join(name);
+        code.push_str(&format!(
+            "#[path = {:?}]\n#[allow(dead_code)]\nmod {module};\n",
+            path.to_string_lossy()
+        ));
+        solutions.push(format!("&{module}::Solution"));
+    }
+
+    code.push_str(&format!(
+        "pub static SOLUTIONS: &[&dyn advent_of_code::template::registry::Solution] = &[{}];\n",
+        solutions.join(", ")
+    ));
+
+    fs::write(out_path, code).unwrap();
+}
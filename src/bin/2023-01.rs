@@ -89,13 +89,11 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(142));
+        advent_of_code::template::check_examples(PUZZLE, 1, part_one);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(141));
+        advent_of_code::template::check_examples(PUZZLE, 2, part_two);
     }
 }
@@ -84,11 +84,11 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(sum)
 }
 
-fn parse_entire_input(input: &str) -> IResult<&str, Vec<Game>> {
+fn parse_entire_input(input: &str) -> IResult<&str, Vec<Game<'_>>> {
     many0(parse_single_line)(input)
 }
 
-fn parse_single_line(input: &str) -> IResult<&str, Game> {
+fn parse_single_line(input: &str) -> IResult<&str, Game<'_>> {
     let (input, _) = tag("Game ")(input)?;
     let (input, number) = map_res(digit1, str::parse)(input)?;
     let (input, _) = tag(": ")(input)?;
@@ -98,15 +98,15 @@ fn parse_single_line(input: &str) -> IResult<&str, Game> {
     Ok((input, Game { number, draws }))
 }
 
-fn parse_game(input: &str) -> IResult<&str, Vec<Vec<Draw>>> {
+fn parse_game(input: &str) -> IResult<&str, Vec<Vec<Draw<'_>>>> {
     separated_list1(tag("; "), parse_draws)(input)
 }
 
-fn parse_draws(input: &str) -> IResult<&str, Vec<Draw>> {
+fn parse_draws(input: &str) -> IResult<&str, Vec<Draw<'_>>> {
     separated_list1(tag(", "), parse_draw)(input)
 }
 
-fn parse_draw(input: &str) -> IResult<&str, Draw> {
+fn parse_draw(input: &str) -> IResult<&str, Draw<'_>> {
     let (input, count) = map_res(digit1, str::parse)(input)?;
     let (input, _) = tag(" ")(input)?;
     let (input, color) = alt((tag("blue"), tag("green"), tag("red")))(input)?;
@@ -120,20 +120,18 @@ mod tests {
 
     #[test]
     fn test_parse_input() {
-        let input = advent_of_code::template::read_file("examples", DAY);
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
         let (remaining, _) = parse_entire_input(&input).unwrap();
         assert_eq!(remaining, "");
     }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(8));
+        advent_of_code::template::check_examples(PUZZLE, 1, part_one);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2286));
+        advent_of_code::template::check_examples(PUZZLE, 2, part_two);
     }
 }
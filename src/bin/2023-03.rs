@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use advent_of_code::grid::{Grid, Pos};
+
+advent_of_code::solution!(3);
+
+#[derive(Debug, PartialEq)]
+enum Cell {
+    Number(i32),
+    Symbol(char),
+    Empty,
+}
+
+fn parse_input(input: &str) -> (Grid<Cell>, HashMap<i32, u32>) {
+    let grid = Grid::parse(input, |c| c).expect("input should be a grid");
+
+    let mut incremental_id = 0;
+    let mut part_id_mappings = HashMap::new();
+    let mut is_processing_part = false;
+
+    // cells are mapped row by row, so digits next to each other share an id.
+    let grid_with_cells = Grid::from_fn(grid.width(), grid.height(), |(x, y)| {
+        // a part ends at the end of its row.
+        if x == 0 && is_processing_part {
+            incremental_id += 1;
+            is_processing_part = false;
+        }
+
+        let cell = grid[(x, y)];
+        if let Some(digit) = cell.to_digit(10) {
+            let part_id = part_id_mappings.entry(incremental_id).or_insert(0);
+            *part_id = *part_id * 10 + digit;
+            is_processing_part = true;
+            return Cell::Number(incremental_id);
+        }
+
+        if is_processing_part {
+            incremental_id += 1;
+        }
+        is_processing_part = false;
+
+        match cell {
+            '.' => Cell::Empty,
+            x => Cell::Symbol(x),
+        }
+    });
+
+    (grid_with_cells, part_id_mappings)
+}
+
+/// Ids of the parts adjacent to `pos`, including diagonally.
+fn adjacent_part_ids(grid: &Grid<Cell>, pos: Pos) -> HashSet<i32> {
+    grid.neighbours8(pos)
+        .filter_map(|neighbour| match grid[neighbour] {
+            Cell::Number(n) => Some(n),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let (grid_with_cells, part_id_mappings) = parse_input(input);
+
+    let mut part_id_is_valid = HashSet::new();
+    for (pos, cell) in grid_with_cells.iter() {
+        if let Cell::Symbol(_) = *cell {
+            part_id_is_valid.extend(adjacent_part_ids(&grid_with_cells, pos));
+        }
+    }
+
+    Some(
+        part_id_mappings
+            .iter()
+            .filter(|(k, _)| part_id_is_valid.contains(*k))
+            .map(|(_, v)| v)
+            .sum(),
+    )
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let (grid_with_cells, part_id_mappings) = parse_input(input);
+
+    let mut gear_ratio_sum = 0;
+    for (pos, cell) in grid_with_cells.iter() {
+        if let Cell::Symbol('*') = *cell {
+        } else {
+            continue;
+        }
+
+        let adjacent_part_ids = adjacent_part_ids(&grid_with_cells, pos);
+
+        if adjacent_part_ids.len() == 2 {
+            // This is a gear
+            let gear_ratio: u32 = adjacent_part_ids
+                .iter()
+                .map(|&adjacent_part_id| part_id_mappings.get(&adjacent_part_id).unwrap())
+                .product();
+            gear_ratio_sum += gear_ratio;
+        }
+    }
+
+    Some(gear_ratio_sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        advent_of_code::template::check_examples(PUZZLE, 1, part_one);
+    }
+
+    #[test]
+    fn test_part_two() {
+        advent_of_code::template::check_examples(PUZZLE, 2, part_two);
+    }
+
+    #[test]
+    fn test_parts_end_at_row_end() {
+        let (_, part_id_mappings) = parse_input("..12\n34..\n");
+        let mut parts: Vec<_> = part_id_mappings.into_values().collect();
+        parts.sort_unstable();
+        assert_eq!(parts, vec![12, 34]);
+    }
+}
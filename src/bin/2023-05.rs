@@ -1,25 +1,30 @@
+use advent_of_code::parse::{blank_line, lines, list, parse_all, unsigned};
 use nom::{
     bytes::complete::tag,
-    character::complete::{digit1, line_ending, multispace0, space1},
-    combinator::{map, map_res},
-    multi::separated_list1,
-    sequence::{separated_pair, tuple},
+    character::complete::{line_ending, space1},
+    combinator::map,
+    sequence::{preceded, separated_pair, tuple},
     IResult,
 };
 use std::collections::BTreeMap;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, parse = parse);
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (_, (seeds, almanac)) = parse_input(input).ok()?;
-    seeds
+pub fn part_one(input: &Input) -> Option<u64> {
+    input
+        .seeds
         .iter()
-        .map(|seed| get_seed_location(*seed, &almanac))
+        .map(|seed| get_seed_location(*seed, &input.almanac))
         .min_by(|x, y| x.cmp(y))
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (_, ParsedInput2(seeds, almanac)) = parse_input_2(input).ok()?;
+pub fn part_two(input: &Input) -> Option<u64> {
+    let Input { seeds, almanac } = input;
+    // in part two, the seed numbers are pairs of range start and length.
+    let seeds = seeds
+        .chunks_exact(2)
+        .map(|pair| (pair[0], pair[0] + pair[1] - 1))
+        .collect();
     let soil = map_ranges_to_next_range(seeds, &almanac.seed_to_soil_map);
     let fertilizer = map_ranges_to_next_range(soil, &almanac.soil_to_fertilizer_map);
     let water = map_ranges_to_next_range(fertilizer, &almanac.fertilizer_to_water_map);
@@ -46,8 +51,13 @@ struct MapEntry {
     destination_start: u64,
 }
 
+pub struct Input {
+    seeds: Vec<u64>,
+    almanac: Almanac,
+}
+
 #[derive(Debug, PartialEq)]
-struct Almanac {
+pub struct Almanac {
     seed_to_soil_map: Vec<(u64, MapEntry)>,
     soil_to_fertilizer_map: Vec<(u64, MapEntry)>,
     fertilizer_to_water_map: Vec<(u64, MapEntry)>,
@@ -118,41 +128,32 @@ fn get_seed_location(seed: u64, almanac: &Almanac) -> u64 {
     location[0].0
 }
 
-fn parse_input(input: &str) -> IResult<&str, (Vec<u64>, Almanac)> {
-    let (input, _) = tag("seeds: ")(input)?;
-    let (input, seeds) = separated_list1(space1, parse_number)(input)?;
-    let (input, _) = multispace0(input)?;
-
-    let (input, almanac) = parse_mappings(input)?;
-
-    Ok((input, (seeds, almanac)))
+pub fn parse(input: &str) -> Option<Input> {
+    let (seeds, almanac) = parse_all(input, parse_input).ok()?;
+    Some(Input { seeds, almanac })
 }
 
-struct ParsedInput2(Vec<(u64, u64)>, Almanac);
-fn parse_input_2(input: &str) -> IResult<&str, ParsedInput2> {
-    let (input, _) = tag("seeds: ")(input)?;
-    let (input, seed_ranges) = separated_list1(
-        space1,
-        map(
-            separated_pair(parse_number, space1, parse_number),
-            |(start, length)| (start, start + length - 1),
-        ),
-    )(input)?;
-    let (input, _) = multispace0(input)?;
-
-    let (input, almanac) = parse_mappings(input)?;
-
-    Ok((input, ParsedInput2(seed_ranges, almanac)))
+fn parse_input(input: &str) -> IResult<&str, (Vec<u64>, Almanac)> {
+    separated_pair(
+        preceded(tag("seeds:"), list(unsigned)),
+        blank_line,
+        parse_mappings,
+    )(input)
 }
 
 fn parse_mappings(input: &str) -> IResult<&str, Almanac> {
-    let (input, seed_to_soil_map) = parse_map("seed-to-soil", input)?;
-    let (input, soil_to_fertilizer_map) = parse_map("soil-to-fertilizer", input)?;
-    let (input, fertilizer_to_water_map) = parse_map("fertilizer-to-water", input)?;
-    let (input, water_to_light_map) = parse_map("water-to-light", input)?;
-    let (input, light_to_temperature_map) = parse_map("light-to-temperature", input)?;
-    let (input, temperature_to_humidity_map) = parse_map("temperature-to-humidity", input)?;
-    let (input, humidity_to_location_map) = parse_map("humidity-to-location", input)?;
+    let (input, seed_to_soil_map) = parse_map("seed-to-soil")(input)?;
+    let (input, soil_to_fertilizer_map) =
+        preceded(blank_line, parse_map("soil-to-fertilizer"))(input)?;
+    let (input, fertilizer_to_water_map) =
+        preceded(blank_line, parse_map("fertilizer-to-water"))(input)?;
+    let (input, water_to_light_map) = preceded(blank_line, parse_map("water-to-light"))(input)?;
+    let (input, light_to_temperature_map) =
+        preceded(blank_line, parse_map("light-to-temperature"))(input)?;
+    let (input, temperature_to_humidity_map) =
+        preceded(blank_line, parse_map("temperature-to-humidity"))(input)?;
+    let (input, humidity_to_location_map) =
+        preceded(blank_line, parse_map("humidity-to-location"))(input)?;
 
     Ok((
         input,
@@ -168,13 +169,11 @@ fn parse_mappings(input: &str) -> IResult<&str, Almanac> {
     ))
 }
 
-fn parse_map<'a>(name: &str, input: &'a str) -> IResult<&'a str, Vec<(u64, MapEntry)>> {
-    let (input, _) = tag(name)(input)?;
-    let (input, _) = tag(" map:")(input)?;
-    let (input, _) = line_ending(input)?;
-    let (input, result) = map(parse_mapping_ranges, map_from_mapping_ranges)(input)?;
-    let (input, _) = multispace0(input)?;
-    Ok((input, result))
+fn parse_map<'a>(name: &'a str) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<(u64, MapEntry)>> {
+    preceded(
+        tuple((tag(name), tag(" map:"), line_ending)),
+        map(parse_mapping_ranges, map_from_mapping_ranges),
+    )
 }
 
 fn map_from_mapping_ranges(ranges: Vec<Range>) -> Vec<(u64, MapEntry)> {
@@ -192,12 +191,12 @@ fn map_from_mapping_ranges(ranges: Vec<Range>) -> Vec<(u64, MapEntry)> {
 }
 
 fn parse_mapping_ranges(input: &str) -> IResult<&str, Vec<Range>> {
-    separated_list1(line_ending, parse_mapping_range)(input)
+    lines(parse_mapping_range)(input)
 }
 
 fn parse_mapping_range(input: &str) -> IResult<&str, Range> {
     let (input, (destination_start, _, source_start, _, length)) =
-        tuple((parse_number, space1, parse_number, space1, parse_number))(input)?;
+        tuple((unsigned, space1, unsigned, space1, unsigned))(input)?;
     Ok((
         input,
         Range {
@@ -208,20 +207,10 @@ fn parse_mapping_range(input: &str) -> IResult<&str, Range> {
     ))
 }
 
-fn parse_number(input: &str) -> IResult<&str, u64> {
-    map_res(digit1, str::parse)(input)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_parse_number() {
-        let result = parse_number("23");
-        assert_eq!(result, Ok(("", 23)));
-    }
-
     #[test]
     fn test_parse_mapping_range() {
         let result = parse_mapping_range("50 98 2");
@@ -279,20 +268,30 @@ mod tests {
 
     #[test]
     fn test_parse_input() {
-        let binding = advent_of_code::template::read_file("examples", DAY);
-        let (result, _) = parse_input(&binding).unwrap();
-        assert_eq!(result, "");
+        let binding = advent_of_code::template::read_file("examples", PUZZLE);
+        let (seeds, almanac) = parse_all(&binding, parse_input).unwrap();
+        assert_eq!(seeds, vec![79, 14, 55, 13]);
+        assert_eq!(almanac.humidity_to_location_map.len(), 2);
     }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(35));
+        advent_of_code::template::check_examples(PUZZLE, 1, |input| part_one(&parse(input)?));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(46));
+        advent_of_code::template::check_examples(PUZZLE, 2, |input| part_two(&parse(input)?));
+    }
+
+    #[cfg(feature = "count-allocs")]
+    #[test]
+    fn test_allocations() {
+        use advent_of_code::template::alloc;
+
+        let input = parse(&advent_of_code::template::read_file("examples", PUZZLE)).unwrap();
+        // part one maps every seed on its own, part two maps all seed ranges at once.
+        alloc::assert_allocations_below(40, || part_one(&input));
+        alloc::assert_allocations_below(16, || part_two(&input));
     }
 }
@@ -0,0 +1,124 @@
+use advent_of_code::math::isqrt;
+use advent_of_code::parse::{list, unsigned};
+use nom::{bytes::complete::tag, character::complete::line_ending, IResult};
+
+advent_of_code::solution!(6);
+
+/// Number of whole milliseconds to hold the button for that beat the record distance.
+fn ways_to_win(race: &RaceResult) -> Option<u64> {
+    let RaceResult { time, distance } = *race;
+
+    // holding the button for `t` travels `t * (time - t)`, which beats the record strictly
+    // between the roots `(time ± sqrt(time² - 4 * distance)) / 2`.
+    let Some(discriminant) = time
+        .checked_mul(time)?
+        .checked_sub(distance.checked_mul(4)?)
+    else {
+        // the record is further than any hold time travels.
+        return Some(0);
+    };
+    // rounding the root down starts at most one step before the first hold time that wins.
+    let mut first = (time - isqrt(discriminant)?) / 2;
+    while first * (time - first) <= distance {
+        first += 1;
+        if first > time / 2 {
+            return Some(0);
+        }
+    }
+
+    // the hold times that win are symmetric around `time / 2`.
+    Some(time - 2 * first + 1)
+}
+
+/// Concatenates the digits of two numbers, e.g. `7` and `15` to `715`.
+fn concat(a: u64, b: u64) -> Option<u64> {
+    let digits = b.checked_ilog10().unwrap_or(0) + 1;
+    a.checked_mul(10_u64.checked_pow(digits)?)?.checked_add(b)
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let (_, races) = parse_input(input).ok()?;
+    races.iter().map(ways_to_win).product()
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let (_, races) = parse_input(input).ok()?;
+
+    let mut actual_race = RaceResult {
+        time: 0,
+        distance: 0,
+    };
+    for race in races {
+        actual_race.time = concat(actual_race.time, race.time)?;
+        actual_race.distance = concat(actual_race.distance, race.distance)?;
+    }
+    ways_to_win(&actual_race)
+}
+
+#[derive(Debug, PartialEq)]
+struct RaceResult {
+    time: u64,
+    distance: u64,
+}
+
+fn parse_input(input: &str) -> IResult<&str, Vec<RaceResult>> {
+    let (input, _) = tag("Time:")(input)?;
+    let (input, times) = list(unsigned)(input)?;
+    let (input, _) = line_ending(input)?;
+    let (input, _) = tag("Distance:")(input)?;
+    let (input, distances) = list(unsigned)(input)?;
+    let (input, _) = line_ending(input)?;
+
+    let mut result = Vec::new();
+    for i in 0..times.len() {
+        result.push(RaceResult {
+            time: times[i],
+            distance: distances[i],
+        });
+    }
+
+    Ok((input, result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser() {
+        let binding = advent_of_code::template::read_file("examples", PUZZLE);
+        let (input, _) = parse_input(&binding).unwrap();
+        // We have parsed the input completely
+        assert_eq!(input, "");
+    }
+
+    #[test]
+    fn test_part_one() {
+        advent_of_code::template::check_examples(PUZZLE, 1, part_one);
+    }
+
+    #[test]
+    fn test_part_two() {
+        advent_of_code::template::check_examples(PUZZLE, 2, part_two);
+    }
+
+    #[test]
+    fn test_ways_to_win() {
+        let ways = |time, distance| ways_to_win(&RaceResult { time, distance });
+        assert_eq!(ways(7, 9), Some(4));
+        // the roots 10 and 20 tie with the record.
+        assert_eq!(ways(30, 200), Some(9));
+        assert_eq!(ways(4, 4), Some(0));
+        assert_eq!(ways(3, 5), Some(0));
+
+        for time in 0..50 {
+            for distance in 0..700 {
+                let expected = (0..=time).filter(|t| t * (time - t) > distance).count();
+                assert_eq!(ways(time, distance), Some(expected as u64));
+            }
+        }
+
+        assert_eq!(concat(7, 15), Some(715));
+        assert_eq!(concat(1, 0), Some(10));
+    }
+}
@@ -1,10 +1,11 @@
 use std::{cmp::Ordering, collections::HashMap};
 
+use advent_of_code::parse::{lines, parse_all, unsigned};
 use nom::{
     branch::alt,
-    character::complete::{char, digit1, line_ending, space1},
-    combinator::{map, map_res},
-    multi::{count, separated_list1},
+    character::complete::{char, space1},
+    combinator::map,
+    multi::count,
     sequence::separated_pair,
     IResult,
 };
@@ -121,7 +122,7 @@ enum Card {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (_, mut hands_with_bid) = parse_input(input).ok()?;
+    let mut hands_with_bid = parse_all(input, parse_input).ok()?;
     hands_with_bid.sort_by_key(|hand_with_bid| hand_with_bid.hand.clone());
     Some(
         hands_with_bid
@@ -134,7 +135,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let (_, mut hands_with_bid) = parse_input_2(input).ok()?;
+    let mut hands_with_bid = parse_all(input, parse_input_2).ok()?;
     hands_with_bid.sort_by_key(|hand_with_bid| hand_with_bid.hand.clone());
     Some(
         hands_with_bid
@@ -147,23 +148,17 @@ pub fn part_two(input: &str) -> Option<u32> {
 }
 
 fn parse_input(input: &str) -> IResult<&str, Vec<HandWithBid>> {
-    separated_list1(
-        line_ending,
-        map(
-            separated_pair(parse_hand, space1, parse_number),
-            |(hand, bid): (Hand, u32)| HandWithBid { hand, bid },
-        ),
-    )(input)
+    lines(map(
+        separated_pair(parse_hand, space1, unsigned),
+        |(hand, bid): (Hand, u32)| HandWithBid { hand, bid },
+    ))(input)
 }
 
 fn parse_input_2(input: &str) -> IResult<&str, Vec<HandWithBid>> {
-    separated_list1(
-        line_ending,
-        map(
-            separated_pair(parse_hand_2, space1, parse_number),
-            |(hand, bid): (Hand, u32)| HandWithBid { hand, bid },
-        ),
-    )(input)
+    lines(map(
+        separated_pair(parse_hand_2, space1, unsigned),
+        |(hand, bid): (Hand, u32)| HandWithBid { hand, bid },
+    ))(input)
 }
 
 fn parse_hand(input: &str) -> IResult<&str, Hand> {
@@ -197,31 +192,24 @@ fn parse_card(j_is_joker: bool) -> impl FnMut(&str) -> IResult<&str, Card> {
     }
 }
 
-fn parse_number(input: &str) -> IResult<&str, u32> {
-    map_res(digit1, str::parse)(input)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_parse_input() {
-        let input = advent_of_code::template::read_file("examples", DAY);
-        let (remaining, _) = parse_input(&input).unwrap();
-        let (remaining, _) = line_ending::<&str, nom::error::Error<&str>>(remaining).unwrap();
-        assert_eq!(remaining, "");
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let hands_with_bid = parse_all(&input, parse_input).unwrap();
+        assert_eq!(hands_with_bid.len(), input.lines().count());
     }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6440));
+        advent_of_code::template::check_examples(PUZZLE, 1, part_one);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(5905));
+        advent_of_code::template::check_examples(PUZZLE, 2, part_two);
     }
 }
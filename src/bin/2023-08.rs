@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+
+use advent_of_code::math::crt;
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, char, line_ending, multispace1},
+    combinator::map,
+    multi::{many1, separated_list1},
+    sequence::{delimited, separated_pair, terminated},
+    IResult,
+};
+
+advent_of_code::solution!(8);
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let (_, (instructions, DirectionMap(direction_map))) = parse_input(input).ok()?;
+    let mut iter = instructions.into_iter().cycle();
+    let mut count = 0;
+    let mut current_node = direction_map.get("AAA")?;
+    while current_node.value != "ZZZ" {
+        let direction = iter.next()?;
+        match direction {
+            Instruction::Left => {
+                current_node = direction_map.get(current_node.left)?;
+            }
+            Instruction::Right => {
+                current_node = direction_map.get(current_node.right)?;
+            }
+        }
+        count += 1;
+    }
+    Some(count)
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let (_, (instructions, DirectionMap(direction_map))) = parse_input(input).ok()?;
+
+    // number the nodes, so states of a ghost can be looked up by index.
+    let names: Vec<&str> = direction_map.keys().copied().collect();
+    let indices: HashMap<&str, usize> = names.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+    let next = names
+        .iter()
+        .map(|name| {
+            let node = &direction_map[name];
+            Some((*indices.get(node.left)?, *indices.get(node.right)?))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let is_end: Vec<bool> = names.iter().map(|name| name.ends_with('Z')).collect();
+
+    let cycles: Vec<Cycle> = names
+        .iter()
+        .enumerate()
+        .filter(|(_, name)| name.ends_with('A'))
+        .map(|(start, _)| Cycle::find(start, &next, &is_end, &instructions))
+        .collect();
+
+    first_common_end(&cycles)
+}
+
+/// The steps at which a ghost is on a node ending in `Z`. The path of a ghost is determined by its
+/// node and position in the instructions, so it loops once such a state repeats.
+#[derive(Debug, PartialEq)]
+struct Cycle {
+    /// Steps before the loop starts at which the ghost is on an end node.
+    ends_before_loop: Vec<u64>,
+    /// Steps in the first round of the loop at which the ghost is on an end node.
+    ends_in_loop: Vec<u64>,
+    /// Step at which the loop starts.
+    start: u64,
+    length: u64,
+}
+
+impl Cycle {
+    fn find(
+        start: usize,
+        next: &[(usize, usize)],
+        is_end: &[bool],
+        instructions: &[Instruction],
+    ) -> Self {
+        let mut first_seen = vec![None; next.len() * instructions.len()];
+        let mut ends = vec![];
+        let mut node = start;
+        let mut step = 0;
+
+        loop {
+            let i = step as usize % instructions.len();
+            let state = node * instructions.len() + i;
+
+            if let Some(loop_start) = first_seen[state] {
+                let (ends_before_loop, ends_in_loop) =
+                    ends.into_iter().partition(|&end| end < loop_start);
+
+                return Cycle {
+                    ends_before_loop,
+                    ends_in_loop,
+                    start: loop_start,
+                    length: step - loop_start,
+                };
+            }
+
+            first_seen[state] = Some(step);
+            if is_end[node] {
+                ends.push(step);
+            }
+
+            node = match instructions[i] {
+                Instruction::Left => next[node].0,
+                Instruction::Right => next[node].1,
+            };
+            step += 1;
+        }
+    }
+
+    fn is_end(&self, step: u64) -> bool {
+        // ends in the loop lie in its first round, so a later step with the same remainder is an end.
+        self.ends_before_loop.contains(&step)
+            || (step >= self.start
+                && self
+                    .ends_in_loop
+                    .iter()
+                    .any(|end| end % self.length == step % self.length))
+    }
+}
+
+/// The first step at which all ghosts are on end nodes at the same time.
+fn first_common_end(cycles: &[Cycle]) -> Option<u64> {
+    // ends before a loop are only reached once, check them directly.
+    let before_loops = cycles
+        .iter()
+        .flat_map(|cycle| &cycle.ends_before_loop)
+        .copied()
+        .filter(|&step| cycles.iter().all(|cycle| cycle.is_end(step)))
+        .min();
+
+    // otherwise, every ghost is at one of its ends in the loop. Each choice of ends is a system of
+    // congruences `step = end (mod length)`, which holds from the latest of the chosen ends on.
+    let mut systems = vec![(0, 1, 0)];
+    for cycle in cycles {
+        systems = systems
+            .iter()
+            .flat_map(|&(remainder, modulus, earliest)| {
+                cycle.ends_in_loop.iter().filter_map(move |&end| {
+                    let (remainder, modulus) = crt([(remainder, modulus), (end, cycle.length)])?;
+                    Some((remainder, modulus, earliest.max(end)))
+                })
+            })
+            .collect();
+    }
+
+    let in_loops = systems
+        .into_iter()
+        .filter_map(|(remainder, modulus, earliest)| {
+            if remainder >= earliest {
+                Some(remainder)
+            } else {
+                (earliest - remainder)
+                    .div_ceil(modulus)
+                    .checked_mul(modulus)?
+                    .checked_add(remainder)
+            }
+        })
+        .min();
+
+    before_loops.into_iter().chain(in_loops).min()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Instruction {
+    Left,
+    Right,
+}
+
+#[derive(Debug, PartialEq)]
+struct Node<'a> {
+    value: &'a str,
+    left: &'a str,
+    right: &'a str,
+}
+struct DirectionMap<'a>(HashMap<&'a str, Node<'a>>);
+
+fn parse_instruction(input: &str) -> IResult<&str, Instruction> {
+    alt((
+        map(char('L'), |_| Instruction::Left),
+        map(char('R'), |_| Instruction::Right),
+    ))(input)
+}
+
+fn parse_direction_map_entry(input: &str) -> IResult<&str, (&str, Node<'_>)> {
+    map(
+        separated_pair(
+            alphanumeric1,
+            tag(" = "),
+            delimited(
+                char('('),
+                separated_pair(alphanumeric1, tag(", "), alphanumeric1),
+                char(')'),
+            ),
+        ),
+        |(value, (left, right))| (value, Node { left, right, value }),
+    )(input)
+}
+
+fn parse_input(input: &str) -> IResult<&str, (Vec<Instruction>, DirectionMap<'_>)> {
+    let (input, instructions) = terminated(many1(parse_instruction), multispace1)(input)?;
+    let (input, direction_map) = terminated(
+        separated_list1(line_ending, parse_direction_map_entry),
+        multispace1,
+    )(input)?;
+
+    Ok((
+        input,
+        (
+            instructions,
+            DirectionMap(direction_map.into_iter().collect()),
+        ),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_instruction() {
+        let (remaining, instruction) = parse_instruction("L").unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(instruction, Instruction::Left);
+
+        let (remaining, instruction) = parse_instruction("R").unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(instruction, Instruction::Right);
+
+        let result = parse_instruction("Z");
+        assert!(result.is_err());
+
+        let (remaining, instructions) =
+            terminated(many1(parse_instruction), multispace1)("LR\n\nAAA = (BBB, CCC)\n").unwrap();
+        assert_eq!(remaining, "AAA = (BBB, CCC)\n");
+        assert_eq!(instructions, vec![Instruction::Left, Instruction::Right]);
+    }
+
+    #[test]
+    fn test_parse_direction_map_entry() {
+        let (remaining, map_entry) = parse_direction_map_entry("AAA = (BBB, CCC)").unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(
+            map_entry,
+            (
+                "AAA",
+                Node {
+                    left: "BBB",
+                    right: "CCC",
+                    value: "AAA",
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let (remaining, _) = parse_input(&input).unwrap();
+        assert_eq!(remaining, "");
+    }
+
+    #[test]
+    fn test_part_one() {
+        advent_of_code::template::check_examples(PUZZLE, 1, part_one);
+    }
+
+    #[test]
+    fn test_part_two() {
+        advent_of_code::template::check_examples(PUZZLE, 2, part_two);
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        // AAA -> BBB -> ZZZ -> BBB -> ...
+        let next = [(1, 1), (2, 2), (1, 1)];
+        let is_end = [false, false, true];
+        let cycle = Cycle::find(0, &next, &is_end, &[Instruction::Left, Instruction::Right]);
+        assert_eq!(
+            cycle,
+            Cycle {
+                ends_before_loop: vec![],
+                ends_in_loop: vec![2],
+                start: 1,
+                length: 2,
+            }
+        );
+        assert!(cycle.is_end(4));
+        assert!(!cycle.is_end(5));
+    }
+
+    #[test]
+    fn test_first_common_end() {
+        let cycle = |ends_before_loop: Vec<u64>, ends_in_loop: Vec<u64>, start, length| Cycle {
+            ends_before_loop,
+            ends_in_loop,
+            start,
+            length,
+        };
+
+        // ends at multiples of the loop lengths.
+        let cycles = [cycle(vec![], vec![4], 1, 4), cycle(vec![], vec![6], 1, 6)];
+        assert_eq!(first_common_end(&cycles), Some(12));
+
+        // ends offset from the loop lengths, with moduli that aren't coprime.
+        let cycles = [cycle(vec![], vec![3], 0, 4), cycle(vec![], vec![5], 0, 6)];
+        assert_eq!(first_common_end(&cycles), Some(11));
+        let cycles = [cycle(vec![], vec![2], 0, 4), cycle(vec![], vec![1], 0, 6)];
+        assert_eq!(first_common_end(&cycles), None);
+
+        // an end before the loop, and a remainder below the start of another loop.
+        let cycles = [cycle(vec![2], vec![7], 5, 3), cycle(vec![], vec![0], 0, 1)];
+        assert_eq!(first_common_end(&cycles), Some(2));
+        let cycles = [cycle(vec![], vec![10], 10, 2), cycle(vec![], vec![1], 1, 1)];
+        assert_eq!(first_common_end(&cycles), Some(10));
+    }
+}
@@ -1,11 +1,5 @@
-use nom::{
-    bytes::complete::tag,
-    character::complete::{digit1, line_ending, space1},
-    combinator::{map_res, opt, recognize},
-    multi::separated_list1,
-    sequence::{preceded, terminated},
-    IResult,
-};
+use advent_of_code::parse::{lines, list, parse_all, signed};
+use nom::IResult;
 
 advent_of_code::solution!(9);
 
@@ -43,7 +37,7 @@ fn get_predictions(sequence: Vec<i64>) -> Option<(i64, i64)> {
 }
 
 pub fn part_one(input: &str) -> Option<i64> {
-    let (_, sequences) = parse_input(input).ok()?;
+    let sequences = parse_all(input, parse_input).ok()?;
     sequences
         .into_iter()
         .map(get_predictions)
@@ -52,7 +46,7 @@ pub fn part_one(input: &str) -> Option<i64> {
 }
 
 pub fn part_two(input: &str) -> Option<i64> {
-    let (_, sequences) = parse_input(input).ok()?;
+    let sequences = parse_all(input, parse_input).ok()?;
     sequences
         .into_iter()
         .map(get_predictions)
@@ -61,16 +55,7 @@ pub fn part_two(input: &str) -> Option<i64> {
 }
 
 fn parse_input(input: &str) -> IResult<&str, Vec<Vec<i64>>> {
-    terminated(
-        separated_list1(
-            line_ending,
-            separated_list1(
-                space1,
-                map_res(recognize(preceded(opt(tag("-")), digit1)), str::parse),
-            ),
-        ),
-        line_ending,
-    )(input)
+    lines(list(signed))(input)
 }
 
 #[cfg(test)]
@@ -85,30 +70,25 @@ mod tests {
 
     #[test]
     fn test_parse_input() {
-        let input = advent_of_code::template::read_file("examples", DAY);
-        let result = parse_input(&input);
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let result = parse_all(&input, parse_input);
         assert_eq!(
             result,
-            Ok((
-                "",
-                vec![
-                    vec![0, 3, 6, 9, 12, 15,],
-                    vec![1, 3, 6, 10, 15, 21],
-                    vec![10, 13, 16, 21, 30, 45]
-                ]
-            ))
+            Ok(vec![
+                vec![0, 3, 6, 9, 12, 15,],
+                vec![1, 3, 6, 10, 15, 21],
+                vec![10, 13, 16, 21, 30, 45]
+            ])
         )
     }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(114));
+        advent_of_code::template::check_examples(PUZZLE, 1, part_one);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
+        advent_of_code::template::check_examples(PUZZLE, 2, part_two);
     }
 }
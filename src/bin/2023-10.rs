@@ -0,0 +1,185 @@
+use std::collections::HashSet;
+
+use advent_of_code::grid::{Direction, Grid, Pos};
+
+advent_of_code::solution!(10, parse = parse);
+
+/// Directions in which the start tile connects to its neighbours.
+fn start_connections(grid: &Grid<TileType>, pos: Pos) -> Vec<Direction> {
+    Direction::ALL
+        .into_iter()
+        .filter(|&direction| {
+            grid.step(pos, direction)
+                .is_some_and(|neighbour| grid[neighbour].connects(direction.opposite()))
+        })
+        .collect()
+}
+
+fn get_next(pos: Pos, grid: &Grid<TileType>, visited: &HashSet<Pos>) -> Option<Pos> {
+    let directions = match &grid[pos] {
+        TileType::Start => start_connections(grid, pos),
+        tile => tile.connections().to_vec(),
+    };
+
+    directions
+        .into_iter()
+        .filter_map(|direction| grid.step(pos, direction))
+        .find(|next| !visited.contains(next))
+}
+
+pub fn part_one(grid: &Grid<TileType>) -> Option<u32> {
+    let start = find_start(grid)?;
+
+    let mut loop_length = 0;
+
+    let mut visited = HashSet::new();
+    visited.insert(start);
+
+    let mut result = get_next(start, grid, &visited);
+
+    while let Some(pos) = result {
+        visited.insert(pos);
+        result = get_next(pos, grid, &visited);
+        loop_length += 1;
+    }
+
+    Some(u32::div_ceil(loop_length, 2))
+}
+
+fn get_tile_type_without_start(grid: &Grid<TileType>, pos: Pos) -> &TileType {
+    if !matches!(grid[pos], TileType::Start) {
+        &grid[pos]
+    } else {
+        let connections = start_connections(grid, pos);
+        let has_connection = |direction| connections.contains(&direction);
+
+        match (
+            has_connection(Direction::North),
+            has_connection(Direction::South),
+            has_connection(Direction::East),
+            has_connection(Direction::West),
+        ) {
+            (true, true, false, false) => &TileType::NorthSouth,
+            (false, false, true, true) => &TileType::EastWest,
+            (true, false, true, false) => &TileType::NorthEast,
+            (true, false, false, true) => &TileType::NorthWest,
+            (false, true, true, false) => &TileType::SouthEast,
+            (false, true, false, true) => &TileType::SouthWest,
+            _ => panic!("Unexpected scenario"),
+        }
+    }
+}
+
+pub fn part_two(grid: &Grid<TileType>) -> Option<u32> {
+    let start = find_start(grid)?;
+
+    let mut visited = HashSet::new();
+    visited.insert(start);
+
+    let mut result = get_next(start, grid, &visited);
+    while let Some(pos) = result {
+        visited.insert(pos);
+        result = get_next(pos, grid, &visited);
+    }
+
+    let mut area = 0;
+    for pos in grid.positions() {
+        if visited.contains(&pos) {
+            continue;
+        }
+
+        // cast a ray to the bottom right, which crosses the loop an odd number of times from the
+        // inside. It touches `L` and `7` corners without crossing them.
+        let mut is_inside = false;
+        for location in grid.ray(pos, (1, 1)) {
+            let tile_type = get_tile_type_without_start(grid, location);
+            if visited.contains(&location)
+                && !matches!(tile_type, TileType::NorthEast | TileType::SouthWest)
+            {
+                is_inside = !is_inside;
+            }
+        }
+
+        if is_inside {
+            area += 1;
+        }
+    }
+    Some(area)
+}
+
+#[derive(Debug)]
+pub enum TileType {
+    NorthSouth,
+    EastWest,
+    NorthEast,
+    NorthWest,
+    SouthWest,
+    SouthEast,
+    Ground,
+    Start,
+}
+
+impl TileType {
+    /// Directions in which a pipe connects to its neighbours. The start tile is unknown.
+    fn connections(&self) -> &'static [Direction] {
+        match self {
+            TileType::NorthSouth => &[Direction::North, Direction::South],
+            TileType::EastWest => &[Direction::East, Direction::West],
+            TileType::NorthEast => &[Direction::North, Direction::East],
+            TileType::NorthWest => &[Direction::North, Direction::West],
+            TileType::SouthWest => &[Direction::South, Direction::West],
+            TileType::SouthEast => &[Direction::South, Direction::East],
+            TileType::Ground | TileType::Start => &[],
+        }
+    }
+
+    fn connects(&self, direction: Direction) -> bool {
+        self.connections().contains(&direction)
+    }
+}
+
+fn find_start(grid: &Grid<TileType>) -> Option<Pos> {
+    grid.find(|tile| matches!(tile, TileType::Start))
+}
+
+fn parse_tile(c: char) -> Option<TileType> {
+    match c {
+        '|' => Some(TileType::NorthSouth),
+        '-' => Some(TileType::EastWest),
+        'L' => Some(TileType::NorthEast),
+        'J' => Some(TileType::NorthWest),
+        '7' => Some(TileType::SouthWest),
+        'F' => Some(TileType::SouthEast),
+        '.' => Some(TileType::Ground),
+        'S' => Some(TileType::Start),
+        _ => None,
+    }
+}
+
+pub fn parse(input: &str) -> Option<Grid<TileType>> {
+    Grid::try_parse(input, parse_tile).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let grid = parse(&input).unwrap();
+        assert_eq!(grid.height(), input.lines().count());
+        assert!(find_start(&grid).is_some());
+        assert!(parse("S-7\n|x|\n").is_none());
+    }
+
+    #[test]
+    fn test_part_one() {
+        advent_of_code::template::check_examples(PUZZLE, 1, |input| part_one(&parse(input)?));
+    }
+
+    #[test]
+    fn test_part_two() {
+        advent_of_code::template::check_examples(PUZZLE, 2, |input| part_two(&parse(input)?));
+    }
+}
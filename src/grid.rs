@@ -0,0 +1,507 @@
+//! A two-dimensional grid, the most common shape of puzzle inputs.
+//!
+//! Positions are `(x, y)` tuples, `x` being the column and `y` the row, with `(0, 0)` in the top
+//! left corner.
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+/// A position in a grid, `(x, y)`.
+pub type Pos = (usize, usize);
+
+/// Offsets of the four orthogonal neighbours, clockwise from the top.
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of all eight neighbours, clockwise from the top left.
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A row is longer or shorter than the first row.
+    RaggedRow {
+        y: usize,
+        width: usize,
+        expected: usize,
+    },
+    /// A character could not be mapped to a cell.
+    InvalidCell { x: usize, y: usize, c: char },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::RaggedRow { y, width, expected } => write!(
+                f,
+                "row {y} has {width} cells, but the grid is {expected} cells wide."
+            ),
+            Error::InvalidCell { x, y, c } => write!(f, "invalid cell `{c}` at ({x}, {y})."),
+        }
+    }
+}
+
+/// The four orthogonal directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// All directions, clockwise from north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// The change of `(x, y)` when moving one step in this direction.
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.turn_right().opposite()
+    }
+}
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// A grid of `width` × `height` cells with the value `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// A grid of `width` × `height` cells with the value of `f` at each position.
+    /// `f` is called row by row, so it can carry state from one cell to the next.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Parse a grid with one row per line, mapping every character to a cell with `f`.
+    /// Returns an error if the lines differ in length.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, Error> {
+        Self::try_parse(input, |c| Some(f(c)))
+    }
+
+    /// Parse a grid like [`Grid::parse`], but with a mapping that can reject characters.
+    /// Returns an error if `f` returns [`None`] for a character.
+    pub fn try_parse(input: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Self, Error> {
+        let mut cells = vec![];
+        let mut width = 0;
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            let len_before = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                cells.push(f(c).ok_or(Error::InvalidCell { x, y, c })?);
+            }
+            let row_width = cells.len() - len_before;
+
+            if y == 0 {
+                width = row_width;
+            } else if row_width != width {
+                return Err(Error::RaggedRow {
+                    y,
+                    width: row_width,
+                    expected: width,
+                });
+            }
+
+            height += 1;
+        }
+
+        Ok(Grid {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns `true` if `(x, y)` lies within the grid.
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// The cell at `(x, y)`, or [`None`] if it lies outside of the grid.
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[self.index_of(pos)])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            let index = self.index_of(pos);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    fn index_of(&self, (x, y): Pos) -> usize {
+        y * self.width + x
+    }
+
+    /// The position `delta` away from `pos`, or [`None`] if it lies outside of the grid.
+    pub fn offset(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// The position one step from `pos` in `direction`, or [`None`] if it lies outside of the grid.
+    pub fn step(&self, pos: Pos, direction: Direction) -> Option<Pos> {
+        self.offset(pos, direction.offset())
+    }
+
+    /// Positions of the orthogonal neighbours of `pos` that lie within the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS_4
+            .into_iter()
+            .filter_map(move |delta| self.offset(pos, delta))
+    }
+
+    /// Positions of the neighbours of `pos`, including diagonals, that lie within the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS_8
+            .into_iter()
+            .filter_map(move |delta| self.offset(pos, delta))
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The position of the first cell, row by row, that satisfies `predicate`.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// The cells of row `y`.
+    ///
+    /// # Panics
+    /// If `y` lies outside of the grid.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} is outside of the grid.");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// All rows, from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// The cells of column `x`, from top to bottom.
+    ///
+    /// # Panics
+    /// If `x` lies outside of the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside of the grid.");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// All columns, from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Positions from `start` in steps of `delta` until the edge of the grid, including `start`.
+    pub fn ray(&self, start: Pos, delta: (isize, isize)) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.contains(start).then_some(start), move |&pos| {
+            self.offset(pos, delta)
+        })
+    }
+
+    /// All diagonals running from the top left to the bottom right, as positions.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = Pos> + '_> {
+        let top = (0..self.width).map(|x| (x, 0));
+        let left = (1..self.height).map(|y| (0, y));
+        top.chain(left).map(|start| self.ray(start, (1, 1)))
+    }
+
+    /// All diagonals running from the top right to the bottom left, as positions.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = Pos> + '_> {
+        let top = (0..self.width).map(|x| (x, 0));
+        let right = (1..self.height).filter_map(|y| Some((self.width.checked_sub(1)?, y)));
+        top.chain(right).map(|start| self.ray(start, (-1, 1)))
+    }
+
+    /// A grid with the result of `f` for every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// The grid mirrored along its main diagonal, i.e. columns become rows.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+
+    /// The grid rotated by 90° clockwise.
+    pub fn rotate_right(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |(x, y)| {
+            self[(y, self.height - 1 - x)].clone()
+        })
+    }
+
+    /// The grid rotated by 90° counter-clockwise.
+    pub fn rotate_left(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |(x, y)| {
+            self[(self.width - 1 - y, x)].clone()
+        })
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    /// # Panics
+    /// If `pos` lies outside of the grid. Use [`Grid::get`] for positions that may.
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside of the grid."))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside of the grid."))
+    }
+}
+
+/// Prints the grid with one row per line and cells next to each other, like puzzle inputs.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Direction, Error, Grid};
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef\n", |c| c).unwrap()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = Grid::parse("12\n34\n56", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(grid.width(), 2);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid[(1, 2)], 6);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+    }
+
+    #[test]
+    fn parses_empty_grids() {
+        let grid = Grid::parse("", |c| c).unwrap();
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.to_string(), "");
+    }
+
+    #[test]
+    fn rejects_ragged_grids() {
+        assert_eq!(
+            Grid::parse("abc\nde\n", |c| c),
+            Err(Error::RaggedRow {
+                y: 1,
+                width: 2,
+                expected: 3
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_cells() {
+        assert_eq!(
+            Grid::try_parse("12\n3x", |c| c.to_digit(10)),
+            Err(Error::InvalidCell { x: 1, y: 1, c: 'x' })
+        );
+    }
+
+    #[test]
+    fn finds_neighbours() {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours8((1, 0)).collect::<Vec<_>>(),
+            vec![(2, 0), (2, 1), (1, 1), (0, 1), (0, 0)]
+        );
+        assert_eq!(grid.neighbours8((1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn steps_in_directions() {
+        let grid = grid();
+        assert_eq!(grid.step((0, 0), Direction::East), Some((1, 0)));
+        assert_eq!(grid.step((0, 0), Direction::North), None);
+        assert_eq!(grid.step((2, 1), Direction::South), None);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::West.turn_right(), Direction::North);
+    }
+
+    #[test]
+    fn iterates_rows_and_columns() {
+        let grid = grid();
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(
+            grid.rows().map(|row| row.len()).collect::<Vec<_>>(),
+            vec![3, 3]
+        );
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+    }
+
+    #[test]
+    fn iterates_diagonals() {
+        let grid = grid();
+        let to_strings = |diagonals: Vec<Vec<_>>| {
+            diagonals
+                .into_iter()
+                .map(|d| d.into_iter().map(|pos| grid[pos]).collect::<String>())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            to_strings(grid.diagonals().map(Iterator::collect).collect()),
+            vec!["ae", "bf", "c", "d"]
+        );
+        assert_eq!(
+            to_strings(grid.anti_diagonals().map(Iterator::collect).collect()),
+            vec!["a", "bd", "ce", "f"]
+        );
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid = grid();
+        assert_eq!(grid.find(|&c| c == 'e'), Some((1, 1)));
+        assert_eq!(grid.find(|&c| c == 'z'), None);
+        assert_eq!(grid.iter().nth(4), Some(((1, 1), &'e')));
+    }
+
+    #[test]
+    fn transforms_grids() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+    }
+
+    #[test]
+    fn updates_cells() {
+        let mut grid = Grid::new(2, 2, '.');
+        grid[(1, 0)] = '#';
+        *grid.get_mut((0, 1)).unwrap() = '#';
+        assert_eq!(grid.get_mut((2, 2)), None);
+        assert_eq!(grid.to_string(), ".#\n#.");
+    }
+}
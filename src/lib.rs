@@ -1 +1,4 @@
+pub mod grid;
+pub mod math;
+pub mod parse;
 pub mod template;
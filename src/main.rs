@@ -1,40 +1,74 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{all, download, examples, read, scaffold, solve, time};
 use args::{parse, AppArguments};
 
+use advent_of_code::template::{migrate, registry::Registry, PuzzleId};
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{Day, Year};
 #[cfg(feature = "today")]
 use std::process;
 
+/// Solutions linked into this binary with the `registry` feature, generated by `build.rs`.
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{migrate, runner::InputSource, Day, Year};
     use std::process;
 
     pub enum AppArguments {
         Download {
+            year: Year,
             day: Day,
         },
         Read {
+            year: Year,
+            day: Day,
+        },
+        Examples {
+            year: Year,
             day: Day,
+            overwrite: bool,
         },
         Scaffold {
+            year: Year,
             day: Day,
             download: bool,
+            template: Option<String>,
+            answer_type: Option<String>,
         },
         Solve {
+            year: Year,
             day: Day,
             release: bool,
             time: bool,
             dhat: bool,
             submit: Option<u8>,
+            force: bool,
+            inputs: bool,
+            examples: bool,
+            watch: bool,
+            input: InputSource,
         },
         All {
+            year: Year,
             release: bool,
             time: bool,
+            verify: bool,
+            accept: bool,
+            inputs: bool,
         },
         Time {
+            year: Year,
             all: bool,
             day: Option<Day>,
+            compare: bool,
+            baseline: Option<String>,
+            against: Option<String>,
+            memory: bool,
+        },
+        Migrate {
+            year: Year,
         },
         #[cfg(feature = "today")]
         Today,
@@ -42,37 +76,114 @@ mod args {
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
+        let subcommand = args.subcommand()?;
+
+        // every command accepts `--year`, falling back to the `AOC_YEAR` environment variable.
+        let year: Option<Year> = args.opt_value_from_str("--year")?.or_else(Year::from_env);
+        let year = || year.ok_or("no year specified. Pass `--year <year>` or set `AOC_YEAR`.");
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
+                year: year()?,
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                verify: args.contains("--verify"),
+                accept: args.contains("--accept"),
+                inputs: args.contains("--inputs"),
             },
             Some("time") => {
                 let all = args.contains("--all");
+                let compare = args.contains("--compare");
+                let baseline = args.opt_value_from_str("--baseline")?;
+                let against = args.opt_value_from_str("--against")?;
+                let memory = args.contains("--memory");
 
                 AppArguments::Time {
+                    year: year()?,
                     all,
                     day: args.opt_free_from_str()?,
+                    compare,
+                    baseline,
+                    against,
+                    memory,
                 }
             }
             Some("download") => AppArguments::Download {
+                year: year()?,
                 day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
+                year: year()?,
                 day: args.free_from_str()?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
+            Some("examples") => AppArguments::Examples {
+                year: year()?,
                 day: args.free_from_str()?,
-                download: args.contains("--download"),
+                overwrite: args.contains("--overwrite"),
             },
-            Some("solve") => AppArguments::Solve {
+            Some("scaffold") => AppArguments::Scaffold {
+                year: year()?,
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
-                dhat: args.contains("--dhat"),
+                download: args.contains("--download"),
+                template: args.opt_value_from_str("--template")?,
+                answer_type: args.opt_value_from_str("--answer-type")?,
             },
+            Some("solve") => {
+                let year = year()?;
+                let day = args.free_from_str()?;
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let force = args.contains("--force");
+                let time = args.contains("--time");
+                let dhat = args.contains("--dhat");
+                let inputs = args.contains("--inputs");
+                let examples = args.contains("--examples");
+                let watch = args.contains("--watch");
+
+                let path: Option<String> = args.opt_value_from_str("--input")?;
+                let input = match path {
+                    Some(path) => InputSource::from_path(&path),
+                    // the example number is optional, so it is read after all other flags.
+                    None if args.contains("--example") => {
+                        InputSource::Example(args.opt_free_from_str()?)
+                    }
+                    None => InputSource::Puzzle,
+                };
+
+                let input_flag = match input {
+                    InputSource::Example(_) => "--example",
+                    _ => "--input",
+                };
+                check_modes(
+                    &[
+                        ("--inputs", inputs),
+                        ("--examples", examples),
+                        ("--watch", watch),
+                    ],
+                    &[
+                        ("--submit", submit.is_some()),
+                        ("--force", force),
+                        ("--time", time),
+                        ("--dhat", dhat),
+                        (input_flag, !input.is_puzzle()),
+                    ],
+                )?;
+
+                AppArguments::Solve {
+                    year,
+                    day,
+                    release,
+                    time,
+                    dhat,
+                    submit,
+                    force,
+                    inputs,
+                    examples,
+                    watch,
+                    input,
+                }
+            }
+            Some("migrate") => AppArguments::Migrate { year: year()? },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -90,41 +201,134 @@ mod args {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
+        // data of the single-year layout is only moved on request.
+        if !matches!(app_args, AppArguments::Migrate { .. }) && migrate::has_legacy_data() {
+            eprintln!("Warning: found data of the single-year layout in `data/`. Run `cargo migrate --year <year>` to move it to `data/<year>`.");
+        }
+
         Ok(app_args)
     }
+
+    /// Flags like `solve --inputs` run a solution in their own way. They exclude each other and
+    /// the `options` of a regular run, which they would otherwise ignore.
+    fn check_modes(
+        modes: &[(&'static str, bool)],
+        options: &[(&'static str, bool)],
+    ) -> Result<(), String> {
+        let is_set = |(flag, set): &(&'static str, bool)| set.then_some(*flag);
+        let mut modes = modes.iter().filter_map(is_set);
+
+        let Some(mode) = modes.next() else {
+            return Ok(());
+        };
+
+        match modes.chain(options.iter().filter_map(is_set)).next() {
+            Some(flag) => Err(format!("`{mode}` can't be combined with `{flag}`.")),
+            None => Ok(()),
+        }
+    }
 }
 
 fn main() {
+    let registry = Registry::new(solutions::SOLUTIONS);
+
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Time { day, all } => time::handle(day, all),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
+            AppArguments::All {
+                year,
+                release,
+                time,
+                verify,
+                accept,
+                inputs,
+            } => {
+                if inputs {
+                    all::handle_inputs(year, release);
+                } else {
+                    all::handle(&registry, year, release, time, verify, accept);
+                }
+            }
+            AppArguments::Time {
+                year,
+                day,
+                all,
+                compare,
+                baseline,
+                against,
+                memory,
+            } => {
+                if let Some(rev) = against {
+                    time::handle_against(year, day, &rev);
+                } else if memory {
+                    time::handle_memory(year, day, all);
+                } else if compare || baseline.is_some() {
+                    time::handle_compare(year, baseline.as_deref());
+                } else {
+                    time::handle(&registry, year, day, all);
+                }
+            }
+            AppArguments::Migrate { year } => migrate::handle(year),
+            AppArguments::Download { year, day } => download::handle(PuzzleId::new(year, day)),
+            AppArguments::Read { year, day } => read::handle(PuzzleId::new(year, day)),
+            AppArguments::Examples {
+                year,
+                day,
+                overwrite,
+            } => examples::handle(PuzzleId::new(year, day), overwrite),
+            AppArguments::Scaffold {
+                year,
+                day,
+                download,
+                template,
+                answer_type,
+            } => {
+                let puzzle = PuzzleId::new(year, day);
+                // download first, so the template can use the title of the puzzle.
+                if download {
+                    download::handle(puzzle);
+                }
+                scaffold::handle(puzzle, template.as_deref(), answer_type);
                 if download {
-                    download::handle(day);
+                    examples::handle(puzzle, false);
                 }
             }
             AppArguments::Solve {
+                year,
                 day,
                 release,
                 time,
                 dhat,
                 submit,
-            } => solve::handle(day, release, time, dhat, submit),
+                force,
+                inputs,
+                examples,
+                watch,
+                input,
+            } => {
+                let puzzle = PuzzleId::new(year, day);
+                if watch {
+                    solve::handle_watch(puzzle, release);
+                } else if examples {
+                    solve::handle_examples(puzzle, release);
+                } else if inputs {
+                    solve::handle_inputs(puzzle, release);
+                } else {
+                    solve::handle(puzzle, release, time, dhat, submit, force, &input);
+                }
+            }
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day);
-                        download::handle(day);
-                        read::handle(day)
+                match Year::today().zip(Day::today()) {
+                    Some((year, day)) => {
+                        let puzzle = PuzzleId::new(year, day);
+                        download::handle(puzzle);
+                        scaffold::handle(puzzle, None, None);
+                        read::handle(puzzle);
+                        examples::handle(puzzle, false);
                     }
                     None => {
                         eprintln!(
@@ -0,0 +1,445 @@
+//! Number theory for puzzles about cycles and large numbers, generic over the primitive integers.
+//!
+//! Functions return `None` instead of overflowing or panicking, e.g. when a result doesn't fit
+//! into the integer type or a modulus isn't positive. Modular arithmetic never overflows, even
+//! for moduli close to the maximum of the type.
+use std::fmt::Debug;
+
+/// A primitive integer, signed or unsigned.
+pub trait Integer: Copy + Ord + Debug {
+    const ZERO: Self;
+    const ONE: Self;
+    const TWO: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    /// The remainder with the sign of `self`, where `MIN % -1` is `0` instead of overflowing.
+    fn wrapping_rem(self, rhs: Self) -> Self;
+    /// The non-negative remainder, or `None` if `rhs` is zero.
+    fn checked_rem_euclid(self, rhs: Self) -> Option<Self>;
+}
+
+/// A primitive signed integer.
+pub trait Signed: Integer {}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const TWO: Self = 2;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            fn checked_div(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_div(self, rhs)
+            }
+
+            fn wrapping_rem(self, rhs: Self) -> Self {
+                <$t>::wrapping_rem(self, rhs)
+            }
+
+            fn checked_rem_euclid(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_rem_euclid(self, rhs)
+            }
+        }
+    )*};
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Signed for i8 {}
+impl Signed for i16 {}
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for i128 {}
+impl Signed for isize {}
+
+fn abs<T: Integer>(n: T) -> Option<T> {
+    if n < T::ZERO {
+        T::ZERO.checked_sub(n)
+    } else {
+        Some(n)
+    }
+}
+
+/// The greatest common divisor, which is never negative. `gcd(0, 0)` is `0`.
+/// Only `None` if the result doesn't fit, e.g. `gcd(i32::MIN, 0)`.
+pub fn gcd<T: Integer>(mut a: T, mut b: T) -> Option<T> {
+    while b != T::ZERO {
+        (a, b) = (b, a.wrapping_rem(b));
+    }
+    abs(a)
+}
+
+/// The least common multiple, which is never negative. It is `0` if either number is `0`.
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    abs(a.checked_div(gcd(a, b)?)?.checked_mul(b)?)
+}
+
+/// Returns `(g, x, y)` with `a * x + b * y = g`, where `g = gcd(a, b)`.
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> Option<(T, T, T)> {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (T::ONE, T::ZERO);
+    let (mut y0, mut y1) = (T::ZERO, T::ONE);
+
+    while r1 != T::ZERO {
+        let q = r0.checked_div(r1)?;
+        (r0, r1) = (r1, r0.checked_sub(q.checked_mul(r1)?)?);
+        (x0, x1) = (x1, x0.checked_sub(q.checked_mul(x1)?)?);
+        (y0, y1) = (y1, y0.checked_sub(q.checked_mul(y1)?)?);
+    }
+
+    if r0 < T::ZERO {
+        let negate = |n: T| T::ZERO.checked_sub(n);
+        Some((negate(r0)?, negate(x0)?, negate(y0)?))
+    } else {
+        Some((r0, x0, y0))
+    }
+}
+
+/// `a + b (mod m)` for `a` and `b` in `0..m`, without overflowing.
+fn add_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    // `m - b` is positive, so it fits even for signed types.
+    let complement = m.checked_sub(b).expect("b is less than m");
+    if a >= complement {
+        a.checked_sub(complement).expect("a is at least m - b")
+    } else {
+        a.checked_add(b).expect("a + b is less than m")
+    }
+}
+
+/// `a - b (mod m)` for `a` and `b` in `0..m`.
+fn sub_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    if a >= b {
+        a.checked_sub(b).expect("a is at least b")
+    } else {
+        add_mod(a, m.checked_sub(b).expect("b is less than m"), m)
+    }
+}
+
+/// `a * b (mod m)` for `a` and `b` in `0..m`, without overflowing.
+fn mul_mod<T: Integer>(mut a: T, mut b: T, m: T) -> T {
+    if let Some(product) = a.checked_mul(b) {
+        return product.wrapping_rem(m);
+    }
+
+    // double and add, which keeps every intermediate value below `m`.
+    let mut result = T::ZERO;
+    while b > T::ZERO {
+        if b.wrapping_rem(T::TWO) == T::ONE {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b = b.checked_div(T::TWO).expect("divisor is two");
+    }
+    result
+}
+
+/// `base ^ exponent (mod modulus)` in `0..modulus`.
+/// `None` if the modulus isn't positive or the exponent is negative.
+pub fn mod_pow<T: Integer>(base: T, mut exponent: T, modulus: T) -> Option<T> {
+    if modulus <= T::ZERO || exponent < T::ZERO {
+        return None;
+    }
+
+    let mut base = base.checked_rem_euclid(modulus)?;
+    // `1 (mod 1)` is `0`.
+    let mut result = T::ONE.wrapping_rem(modulus);
+    while exponent > T::ZERO {
+        if exponent.wrapping_rem(T::TWO) == T::ONE {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent = exponent.checked_div(T::TWO)?;
+    }
+    Some(result)
+}
+
+/// The `x` in `0..modulus` with `a * x = 1 (mod modulus)`.
+/// `None` if the modulus isn't positive or `a` and the modulus aren't coprime.
+pub fn mod_inverse<T: Integer>(a: T, modulus: T) -> Option<T> {
+    if modulus <= T::ZERO {
+        return None;
+    }
+    if modulus == T::ONE {
+        return Some(T::ZERO);
+    }
+
+    // the extended Euclidean algorithm, with the coefficients of `a` kept modulo `modulus`.
+    let (mut r0, mut r1) = (modulus, a.checked_rem_euclid(modulus)?);
+    let (mut x0, mut x1) = (T::ZERO, T::ONE);
+    while r1 != T::ZERO {
+        let q = r0.checked_div(r1)?;
+        (r0, r1) = (r1, r0.wrapping_rem(r1));
+        let q = q.wrapping_rem(modulus);
+        (x0, x1) = (x1, sub_mod(x0, mul_mod(q, x1, modulus), modulus));
+    }
+
+    (r0 == T::ONE).then_some(x0)
+}
+
+/// Solves a system of congruences `x = remainder (mod modulus)` with the Chinese remainder
+/// theorem. The moduli don't need to be coprime.
+///
+/// Returns the smallest non-negative solution and the least common multiple of the moduli, so
+/// the solutions are `x + k * lcm`. An empty system is solved by every number, i.e. `(0, 1)`.
+/// `None` if there is no solution, a modulus isn't positive, or the least common multiple
+/// doesn't fit.
+pub fn crt<T: Integer>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+    congruences
+        .into_iter()
+        .try_fold((T::ZERO, T::ONE), |(a, m), (b, n)| {
+            if n <= T::ZERO {
+                return None;
+            }
+            let b = b.checked_rem_euclid(n)?;
+
+            // `x = a + m * k`, which requires `m * k = b - a (mod n)`. This has a solution if
+            // the gcd of `m` and `n` divides `b - a`, and is unique modulo `n / g`.
+            let g = gcd(m, n)?;
+            let difference = sub_mod(b, a.checked_rem_euclid(n)?, n);
+            if difference.wrapping_rem(g) != T::ZERO {
+                return None;
+            }
+
+            let reduced = n.checked_div(g)?;
+            let inverse = mod_inverse(m.checked_div(g)?.wrapping_rem(reduced), reduced)?;
+            let k = mul_mod(
+                difference.checked_div(g)?.wrapping_rem(reduced),
+                inverse,
+                reduced,
+            );
+
+            // `a + m * k` is below `m * (n / g)`, so it fits if the least common multiple does.
+            let lcm = m.checked_mul(reduced)?;
+            Some((a.checked_add(m.checked_mul(k)?)?, lcm))
+        })
+}
+
+/// The integer square root, i.e. the largest `r` with `r * r <= n`.
+/// `None` if `n` is negative.
+pub fn isqrt<T: Integer>(n: T) -> Option<T> {
+    if n < T::ZERO {
+        return None;
+    }
+
+    // binary search with `low * low <= n < (high + 1) * (high + 1)`.
+    let (mut low, mut high) = (T::ZERO, n);
+    while low < high {
+        // rounded up, so `mid` is positive and the search always progresses.
+        let mid = high.checked_sub(high.checked_sub(low)?.checked_div(T::TWO)?)?;
+        if mid <= n.checked_div(mid)? {
+            low = mid;
+        } else {
+            high = mid.checked_sub(T::ONE)?;
+        }
+    }
+    Some(low)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{crt, extended_gcd, gcd, isqrt, lcm, mod_inverse, mod_pow};
+
+    /// Deterministic pseudo-random numbers (xorshift), so property tests are reproducible.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        /// A number in `1..=max`.
+        fn below(&mut self, max: u64) -> u64 {
+            self.next() % max + 1
+        }
+
+        /// A number of a random magnitude, so small and large values are both common.
+        fn any(&mut self) -> u64 {
+            let bits = self.next() % 64;
+            self.next() >> bits
+        }
+    }
+
+    const CASES: usize = 2000;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), Some(6));
+        assert_eq!(gcd(-12, 18), Some(6));
+        assert_eq!(gcd(0, 0), Some(0));
+        assert_eq!(gcd(0, -5), Some(5));
+        assert_eq!(gcd(i32::MIN, -1), Some(1));
+        assert_eq!(gcd(i32::MIN, 0), None);
+        assert_eq!(lcm(4u8, 6), Some(12));
+        assert_eq!(lcm(-4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(200u8, 3), None);
+        assert_eq!(lcm(u64::MAX, u64::MAX), Some(u64::MAX));
+    }
+
+    #[test]
+    fn gcd_divides_and_lcm_is_multiple() {
+        let mut rng = Rng(1);
+        for _ in 0..CASES {
+            let (a, b) = (rng.any(), rng.any());
+            let g = gcd(a, b).unwrap();
+            if g == 0 {
+                assert_eq!((a, b), (0, 0));
+                continue;
+            }
+            assert_eq!((a % g, b % g), (0, 0));
+            // the cofactors share no divisor, otherwise `g` wasn't the greatest.
+            assert_eq!(gcd(a / g, b / g), Some(1));
+
+            match lcm(a, b) {
+                Some(l) => {
+                    assert!(a == 0 || b == 0 || (l % a == 0 && l % b == 0));
+                    assert_eq!(u128::from(g) * u128::from(l), u128::from(a) * u128::from(b));
+                }
+                None => assert!(u128::from(a / g) * u128::from(b) > u128::from(u64::MAX)),
+            }
+        }
+    }
+
+    #[test]
+    fn extended_gcd_is_bezout_identity() {
+        assert_eq!(extended_gcd(240, 46), Some((2, -9, 47)));
+        assert_eq!(extended_gcd(-4, 0), Some((4, -1, 0)));
+        assert_eq!(extended_gcd(i8::MIN, 0), None);
+
+        let mut rng = Rng(2);
+        for _ in 0..CASES {
+            let a = rng.any() as i64 >> 1;
+            let b = -(rng.any() as i64 >> 1);
+            let (g, x, y) = extended_gcd(a, b).unwrap();
+            assert_eq!(Some(g), gcd(a, b));
+            assert_eq!(
+                i128::from(a) * i128::from(x) + i128::from(b) * i128::from(y),
+                i128::from(g)
+            );
+        }
+    }
+
+    #[test]
+    fn mod_pow_matches_repeated_multiplication() {
+        assert_eq!(mod_pow(2, 10, 1000), Some(24));
+        assert_eq!(mod_pow(-2, 3, 5), Some(2));
+        assert_eq!(mod_pow(7, 0, 1), Some(0));
+        assert_eq!(mod_pow(2, -1, 5), None);
+        assert_eq!(mod_pow(2, 1, 0), None);
+        // Fermat's little theorem, with a modulus that overflows naive multiplication.
+        let p = 18_446_744_073_709_551_557u64;
+        assert_eq!(mod_pow(u64::MAX - 7, p - 1, p), Some(1));
+
+        let mut rng = Rng(3);
+        for _ in 0..CASES {
+            let (base, exponent, modulus) = (rng.any(), rng.below(50), rng.any().max(1));
+            let expected = (0..exponent).fold(1 % u128::from(modulus), |acc, _| {
+                acc * u128::from(base) % u128::from(modulus)
+            });
+            assert_eq!(
+                mod_pow(base, exponent, modulus).map(u128::from),
+                Some(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn mod_inverse_multiplies_to_one() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 8), None);
+        assert_eq!(mod_inverse(5, 1), Some(0));
+        assert_eq!(mod_inverse(5, -7), None);
+        assert_eq!(mod_inverse(i64::MAX - 1, i64::MAX), Some(i64::MAX - 1));
+
+        let mut rng = Rng(4);
+        for _ in 0..CASES {
+            let (a, modulus) = (rng.any(), rng.any().max(2));
+            match mod_inverse(a, modulus) {
+                Some(x) => {
+                    assert!(x < modulus);
+                    assert_eq!(u128::from(a) * u128::from(x) % u128::from(modulus), 1);
+                }
+                None => assert_ne!(gcd(a, modulus), Some(1)),
+            }
+        }
+    }
+
+    #[test]
+    fn crt_solves_every_congruence() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt([(2, 4), (1, 6)]), None);
+        assert_eq!(crt([(-1, 4)]), Some((3, 4)));
+        assert_eq!(crt([(1, 0)]), None);
+        assert_eq!(crt(Vec::<(u64, u64)>::new()), Some((0, 1)));
+        assert_eq!(crt([(0u8, 16), (0, 17)]), None);
+
+        let mut rng = Rng(5);
+        for _ in 0..CASES {
+            // small moduli often have no common solution, which is checked exhaustively.
+            let max_modulus = if rng.next() % 2 == 0 { 12 } else { 1 << 20 };
+            let congruences: Vec<(u64, u64)> = (0..rng.below(4))
+                .map(|_| (rng.any(), rng.below(max_modulus)))
+                .collect();
+            let solves = |x: u64| congruences.iter().all(|&(r, m)| x % m == r % m);
+
+            match crt(congruences.iter().copied()) {
+                Some((x, l)) => {
+                    assert!(x < l);
+                    assert!(solves(x));
+                    let moduli = congruences.iter().map(|&(_, m)| m);
+                    assert_eq!(moduli.fold(Some(1), |acc, m| lcm(acc?, m)), Some(l));
+                }
+                None if max_modulus == 12 => {
+                    let product = congruences.iter().map(|&(_, m)| m).product::<u64>();
+                    assert!(!(0..product).any(solves));
+                }
+                // the least common multiple doesn't fit, or there is no solution.
+                None => {}
+            }
+        }
+    }
+
+    #[test]
+    fn isqrt_is_floor_of_root() {
+        assert_eq!(isqrt(0), Some(0));
+        assert_eq!(isqrt(1), Some(1));
+        assert_eq!(isqrt(15), Some(3));
+        assert_eq!(isqrt(16), Some(4));
+        assert_eq!(isqrt(-1), None);
+        assert_eq!(isqrt(u64::MAX), Some(u64::from(u32::MAX)));
+        assert_eq!(isqrt(i8::MAX), Some(11));
+
+        let mut rng = Rng(6);
+        for _ in 0..CASES {
+            let n = rng.any();
+            let r = u128::from(isqrt(n).unwrap());
+            assert!(r * r <= u128::from(n));
+            assert!((r + 1) * (r + 1) > u128::from(n));
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//! Parsers for recurring parts of puzzle inputs, built from [`nom`] combinators.
+//!
+//! Parsers for lines and sections don't consume trailing line endings, [`parse_all`] and
+//! [`whole`] take care of these.
+use std::{fmt::Display, str::FromStr};
+
+use nom::{
+    branch::alt,
+    character::complete::{char, digit1, line_ending, multispace0, one_of, space0, space1},
+    combinator::{eof, map_res, opt, recognize},
+    error::ErrorKind,
+    multi::{many1, separated_list1},
+    sequence::{delimited, pair, preceded, terminated},
+    IResult,
+};
+
+/// An unsigned number, e.g. `42`.
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse)(input)
+}
+
+/// A number with an optional sign, e.g. `-42` or `+42`.
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), |s: &str| {
+        s.trim_start_matches('+').parse()
+    })(input)
+}
+
+/// Items on a single line separated by spaces, tabs or commas, e.g. `1, 2 3`.
+/// Leading spaces are skipped, so lists can follow a label like `Time:   7  15`.
+pub fn list<'a, O>(
+    item: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    preceded(space0, separated_list1(list_separator, item))
+}
+
+/// A comma with optional spaces around it, or spaces.
+fn list_separator(input: &str) -> IResult<&str, &str> {
+    alt((recognize(delimited(space0, char(','), space0)), space1))(input)
+}
+
+/// Items on consecutive lines, one per line.
+pub fn lines<'a, O>(
+    item: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(line_ending, item)
+}
+
+/// The end of a line followed by one or more empty lines, which separates sections of an input.
+pub fn blank_line(input: &str) -> IResult<&str, &str> {
+    recognize(pair(line_ending, many1(line_ending)))(input)
+}
+
+/// Sections separated by blank lines.
+pub fn sections<'a, O>(
+    section: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(blank_line, section)
+}
+
+/// Requires `parser` to consume the whole input, except for trailing whitespace.
+pub fn whole<'a, O>(
+    parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O> {
+    terminated(parser, pair(multispace0, eof))
+}
+
+/// Where and why an input could not be parsed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Error {
+    /// Line of the error, starting at 1.
+    pub line: usize,
+    /// Column of the error in characters, starting at 1.
+    pub column: usize,
+    /// The parser that failed.
+    pub kind: ErrorKind,
+    /// The rest of the line at the error.
+    pub found: String,
+}
+
+impl Error {
+    /// Locate the error of a parser that stopped at `remaining` in `input`.
+    fn new(input: &str, remaining: &str, kind: ErrorKind) -> Self {
+        let offset = input.len() - remaining.len();
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        Error {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            kind,
+            found: remaining.lines().next().unwrap_or_default().to_string(),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let found = if self.found.is_empty() {
+            "end of line".into()
+        } else {
+            format!("`{}`", self.found)
+        };
+
+        write!(
+            f,
+            "could not parse input at line {}, column {} ({}): found {found}",
+            self.line,
+            self.column,
+            self.kind.description()
+        )
+    }
+}
+
+/// Parse the whole input with `parser`, ignoring trailing whitespace, see [`whole`].
+pub fn parse_all<'a, O>(
+    input: &'a str,
+    parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> Result<O, Error> {
+    match whole(parser)(input) {
+        Ok((_, output)) => Ok(output),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(Error::new(input, e.input, e.code)),
+        // only streaming parsers ask for more input.
+        Err(nom::Err::Incomplete(_)) => Err(Error::new(input, "", ErrorKind::Complete)),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use nom::{bytes::complete::tag, error::ErrorKind, sequence::preceded};
+
+    use super::{lines, list, parse_all, sections, signed, unsigned, whole, Error};
+
+    #[test]
+    fn parses_numbers() {
+        assert_eq!(unsigned::<u64>("42 rest"), Ok((" rest", 42)));
+        assert_eq!(signed::<i32>("-42"), Ok(("", -42)));
+        assert_eq!(signed::<i32>("+42"), Ok(("", 42)));
+        assert!(unsigned::<u32>("-42").is_err());
+        // out of range for the type.
+        assert!(unsigned::<u8>("256").is_err());
+    }
+
+    #[test]
+    fn parses_lists() {
+        assert_eq!(list(unsigned::<u32>)("1 2  3"), Ok(("", vec![1, 2, 3])));
+        assert_eq!(list(unsigned::<u32>)("1,2, 3"), Ok(("", vec![1, 2, 3])));
+        assert_eq!(list(unsigned::<u32>)("  7\t15"), Ok(("", vec![7, 15])));
+        // lists end at the end of a line.
+        assert_eq!(list(unsigned::<u32>)("1 2\n3"), Ok(("\n3", vec![1, 2])));
+        // a trailing separator is not consumed.
+        assert_eq!(list(unsigned::<u32>)("1 2 "), Ok((" ", vec![1, 2])));
+    }
+
+    #[test]
+    fn parses_lines_and_sections() {
+        let input = "1 2\n3\n\n\n4\n";
+        let result = sections(lines(list(unsigned::<u32>)))(input);
+        assert_eq!(
+            result,
+            Ok(("\n", vec![vec![vec![1, 2], vec![3]], vec![vec![4]]]))
+        );
+        assert_eq!(lines(unsigned::<u32>)("1\r\n2"), Ok(("", vec![1, 2])));
+    }
+
+    #[test]
+    fn requires_whole_input() {
+        assert_eq!(
+            whole(lines(unsigned::<u32>))("1\n2\n\n"),
+            Ok(("", vec![1, 2]))
+        );
+        assert!(whole(lines(unsigned::<u32>))("1\n2\nx").is_err());
+    }
+
+    #[test]
+    fn parses_all() {
+        let parser = preceded(tag("seeds:"), list(unsigned::<u64>));
+        assert_eq!(parse_all("seeds: 1 2\n", parser), Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn locates_errors() {
+        let error = parse_all("1 2\n3 x4\n", lines(list(unsigned::<u32>))).unwrap_err();
+        assert_eq!(
+            error,
+            Error {
+                line: 2,
+                column: 3,
+                kind: ErrorKind::Eof,
+                found: "x4".into(),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "could not parse input at line 2, column 3 (End of file): found `x4`"
+        );
+
+        let error = parse_all("ä: x", preceded(tag("ä: "), unsigned::<u32>)).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 4));
+        assert_eq!(error.kind, ErrorKind::Digit);
+    }
+}
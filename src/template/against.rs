@@ -0,0 +1,401 @@
+//! Benchmarks solutions against another git revision.
+//!
+//! The other revision is checked out into a git worktree below `target/against`, so its build
+//! artifacts are kept between comparisons. Both revisions are built, then every day is benchmarked
+//! alternately with both builds, so both see the same conditions on the machine. Each build runs
+//! with `--time`, i.e. with the benchmark of [`crate::template::runner`].
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use crate::template::{
+    report::{PartReport, Stage, Status},
+    run_multi::{self, child_commands},
+    stats::{format_nanos, BenchStats, Change},
+    Day, PuzzleId, Year, ANSI_ITALIC, ANSI_RESET,
+};
+
+/// Name of the build of the working tree.
+const CURRENT: &str = "working tree";
+
+/// How often each build is benchmarked. Rounds alternate which build runs first.
+const ROUNDS: usize = 3;
+
+#[derive(Debug)]
+pub enum Error {
+    Git(String),
+    Build(String),
+    IO(io::Error),
+    Run(run_multi::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<run_multi::Error> for Error {
+    fn from(e: run_multi::Error) -> Self {
+        Error::Run(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Git(e) => write!(f, "git failed: {e}"),
+            Error::Build(e) => write!(f, "could not build {e}"),
+            Error::IO(e) => write!(f, "{e}"),
+            Error::Run(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// The benchmarks of a stage with both builds.
+#[derive(Clone, Debug)]
+pub struct StageAgainst {
+    pub day: Day,
+    pub stage: Stage,
+    pub baseline: BenchStats,
+    pub current: BenchStats,
+}
+
+impl StageAgainst {
+    /// How many times faster the current build is, e.g. `2.0` if it takes half the time.
+    pub fn speedup(&self) -> f64 {
+        self.baseline.median / self.current.median
+    }
+
+    pub fn change(&self) -> Change {
+        self.current.compare(&self.baseline)
+    }
+}
+
+/// The target directory of cargo, e.g. `target`.
+fn target_dir() -> PathBuf {
+    env::var_os("CARGO_TARGET_DIR").map_or_else(|| PathBuf::from("target"), PathBuf::from)
+}
+
+fn git(args: &[&str]) -> Result<String, Error> {
+    let output = Command::new("git").args(args).output()?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(Error::Git(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+/// A checkout of another revision. Removed when dropped.
+pub struct Worktree {
+    pub path: PathBuf,
+    /// Abbreviated hash of the checked out commit.
+    pub commit: String,
+}
+
+impl Worktree {
+    /// Check out `rev` into `target/against/worktree`, replacing an earlier checkout.
+    pub fn create(rev: &str) -> Result<Self, Error> {
+        let commit = git(&["rev-parse", "--short", &format!("{rev}^{{commit}}")])?;
+        let path = env::current_dir()?
+            .join(target_dir())
+            .join("against")
+            .join("worktree");
+
+        // the worktree of an interrupted comparison is still registered.
+        if path.exists() {
+            let _ = git(&["worktree", "remove", "--force", &path.to_string_lossy()]);
+            let _ = fs::remove_dir_all(&path);
+        }
+        git(&["worktree", "prune"])?;
+        git(&[
+            "worktree",
+            "add",
+            "--detach",
+            &path.to_string_lossy(),
+            &commit,
+        ])?;
+
+        Ok(Self { path, commit })
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        if let Err(e) = git(&[
+            "worktree",
+            "remove",
+            "--force",
+            &self.path.to_string_lossy(),
+        ]) {
+            eprintln!("Failed to remove worktree \"{}\": {e}", self.path.display());
+        }
+    }
+}
+
+/// Copy all files below `from` to `to`, keeping existing files.
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else if !target.exists() {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+/// A build of the solutions, either of the working tree or of a worktree.
+pub struct Build {
+    /// Name of the build in output, e.g. the commit.
+    pub name: String,
+    /// Directory the solutions run in, i.e. the root of a checkout.
+    pub dir: PathBuf,
+    /// Cargo target directory of the build.
+    pub target_dir: PathBuf,
+}
+
+impl Build {
+    /// Build the solutions of `puzzles` in release mode.
+    fn compile(
+        name: &str,
+        dir: &Path,
+        target_dir: &Path,
+        puzzles: &[PuzzleId],
+    ) -> Result<Self, Error> {
+        println!("Building {name}...");
+
+        let mut cmd = Command::new("cargo");
+        cmd.current_dir(dir)
+            .env("CARGO_TARGET_DIR", target_dir)
+            .args(["build", "--quiet", "--release"])
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit());
+
+        for puzzle in puzzles {
+            cmd.args(["--bin", &puzzle.to_string()]);
+        }
+
+        if !cmd.status()?.success() {
+            return Err(Error::Build(dir.display().to_string()));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            dir: dir.to_path_buf(),
+            target_dir: target_dir.to_path_buf(),
+        })
+    }
+
+    /// Benchmark the solution of a puzzle and return the reports of all solved stages.
+    fn bench(&self, puzzle: PuzzleId) -> Result<Vec<PartReport>, Error> {
+        let bin = self
+            .target_dir
+            .join("release")
+            .join(format!("{puzzle}{}", env::consts::EXE_SUFFIX));
+
+        println!("{ANSI_ITALIC}{}{ANSI_RESET}", self.name);
+        let reports = child_commands::run_binary(puzzle, &bin, &self.dir, true)?;
+        Ok(reports
+            .into_iter()
+            .filter(|r| r.status == Status::Solved && r.stats.is_some())
+            .collect())
+    }
+}
+
+/// Prepare the working tree and a checkout of `worktree` for benchmarking `days`.
+/// Returns the days solved in both revisions with the two builds.
+pub fn build(
+    year: Year,
+    days: &[Day],
+    worktree: &Worktree,
+) -> Result<(Vec<Day>, Build, Build), Error> {
+    let days: Vec<Day> = days
+        .iter()
+        .copied()
+        .filter(|day| {
+            let puzzle = PuzzleId::new(year, *day);
+            Path::new(&puzzle.bin_path()).exists() && worktree.path.join(puzzle.bin_path()).exists()
+        })
+        .collect();
+    let puzzles: Vec<PuzzleId> = days.iter().map(|day| PuzzleId::new(year, *day)).collect();
+
+    // inputs are not committed, so the checkout uses the inputs of the working tree.
+    let inputs = PathBuf::from(year.data_dir()).join("inputs");
+    if inputs.exists() {
+        copy_dir(&inputs, &worktree.path.join(&inputs))?;
+    }
+
+    let current_dir = env::current_dir()?;
+    let current_target = current_dir.join(target_dir());
+    let baseline_target = current_target.join("against").join("target");
+
+    // without `--bin` arguments, cargo would build every binary.
+    if puzzles.is_empty() {
+        let current = Build {
+            name: CURRENT.into(),
+            dir: current_dir,
+            target_dir: current_target,
+        };
+        let baseline = Build {
+            name: worktree.commit.clone(),
+            dir: worktree.path.clone(),
+            target_dir: baseline_target,
+        };
+        return Ok((days, current, baseline));
+    }
+
+    let current = Build::compile(CURRENT, &current_dir, &current_target, &puzzles)?;
+    let baseline = Build::compile(&worktree.commit, &worktree.path, &baseline_target, &puzzles)?;
+
+    Ok((days, current, baseline))
+}
+
+/// Benchmark a day alternately with both builds and pair the stages of both.
+///
+/// Of the [`ROUNDS`] benchmarks of each stage, the one with the median median is used.
+pub fn bench_day(
+    puzzle: PuzzleId,
+    current: &Build,
+    baseline: &Build,
+) -> Result<Vec<StageAgainst>, Error> {
+    let mut current_reports: Vec<PartReport> = vec![];
+    let mut baseline_reports: Vec<PartReport> = vec![];
+
+    for round in 0..ROUNDS {
+        if round % 2 == 0 {
+            current_reports.extend(current.bench(puzzle)?);
+            baseline_reports.extend(baseline.bench(puzzle)?);
+        } else {
+            baseline_reports.extend(baseline.bench(puzzle)?);
+            current_reports.extend(current.bench(puzzle)?);
+        }
+    }
+
+    let stages = [Stage::Parse, Stage::Part(1), Stage::Part(2)];
+    Ok(stages
+        .into_iter()
+        .filter_map(|stage| {
+            Some(StageAgainst {
+                day: puzzle.day,
+                stage,
+                baseline: median_stats(&baseline_reports, stage)?,
+                current: median_stats(&current_reports, stage)?,
+            })
+        })
+        .collect())
+}
+
+/// Returns the statistics of the benchmark of `stage` with the median median.
+fn median_stats(reports: &[PartReport], stage: Stage) -> Option<BenchStats> {
+    let mut stats: Vec<&BenchStats> = reports
+        .iter()
+        .filter(|r| r.stage == stage)
+        .filter_map(|r| r.stats.as_ref())
+        .collect();
+
+    stats.sort_unstable_by(|a, b| a.median.total_cmp(&b.median));
+    stats.get(stats.len() / 2).map(|stats| (*stats).clone())
+}
+
+/// Format a stage as a table row.
+pub fn format_stage(stage: &StageAgainst) -> String {
+    let stage_name = match stage.stage {
+        Stage::Parse => "Parse".to_string(),
+        Stage::Part(part) => format!("Part {part}"),
+    };
+
+    let speedup = stage.speedup();
+    let speedup = if speedup >= 1.0 {
+        format!("{speedup:.2}× faster")
+    } else {
+        format!("{:.2}× slower", 1.0 / speedup)
+    };
+
+    let verdict = match stage.change() {
+        Change::Regression(_) => "▲ regression",
+        Change::Improvement(_) => "▼ improvement",
+        Change::Unchanged(_) => "",
+    };
+
+    format!(
+        "{:<6} {:<8} {:>12} {:>12}   {speedup:<14} {verdict}",
+        stage.day.to_string(),
+        stage_name,
+        format_nanos(stage.baseline.median),
+        format_nanos(stage.current.median),
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_stage, median_stats, StageAgainst};
+    use crate::day;
+    use crate::template::report::{PartReport, Stage, Status};
+    use crate::template::stats::BenchStats;
+
+    fn stats(nanos: &[u64]) -> BenchStats {
+        let samples: Vec<Duration> = nanos.iter().map(|n| Duration::from_nanos(*n)).collect();
+        BenchStats::from_samples(&samples, 0).unwrap()
+    }
+
+    fn report(stage: Stage, nanos: &[u64]) -> PartReport {
+        let stats = stats(nanos);
+        PartReport {
+            day: day!(1),
+            stage,
+            status: Status::Solved,
+            answer: None,
+            nanos: stats.median,
+            samples: stats.samples,
+            stats: Some(stats),
+        }
+    }
+
+    #[test]
+    fn picks_median_round() {
+        let reports = [
+            report(Stage::Part(1), &[30, 30, 30]),
+            report(Stage::Part(1), &[10, 10, 10]),
+            report(Stage::Part(2), &[50, 50, 50]),
+            report(Stage::Part(1), &[20, 20, 20]),
+        ];
+        assert_eq!(median_stats(&reports, Stage::Part(1)).unwrap().median, 20.0);
+        assert_eq!(median_stats(&reports, Stage::Part(2)).unwrap().median, 50.0);
+        assert!(median_stats(&reports, Stage::Parse).is_none());
+    }
+
+    #[test]
+    fn formats_speedups() {
+        let stage = StageAgainst {
+            day: day!(1),
+            stage: Stage::Part(1),
+            baseline: stats(&[200, 201, 199, 200, 202, 198]),
+            current: stats(&[100, 101, 99, 100, 102, 98]),
+        };
+        assert!((stage.speedup() - 2.0).abs() < 1e-9);
+        assert!(format_stage(&stage).contains("2.00× faster"));
+        assert!(format_stage(&stage).contains("improvement"));
+
+        let stage = StageAgainst {
+            baseline: stage.current.clone(),
+            current: stage.baseline.clone(),
+            ..stage
+        };
+        assert!(format_stage(&stage).contains("2.00× slower"));
+        assert!(format_stage(&stage).contains("regression"));
+    }
+}
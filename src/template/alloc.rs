@@ -0,0 +1,205 @@
+//! A lightweight global allocator that counts the allocations of every thread, enabled with the
+//! `count-allocs` feature. Solution tests can use it to assert allocation budgets, e.g. that a
+//! part allocates fewer than `n` times or not at all after parsing:
+//!
+//! ```ignore
+//! #[cfg(feature = "count-allocs")]
+//! #[test]
+//! fn test_part_one_allocations() {
+//!     use advent_of_code::template::alloc;
+//!     let input = parse(&advent_of_code::template::read_file("examples", PUZZLE)).unwrap();
+//!     alloc::assert_no_allocations(|| part_one(&input));
+//! }
+//! ```
+//!
+//! Counts are kept per thread, so tests running in parallel don't affect each other.
+//! The allocator is not installed together with `dhat-heap`, which brings its own.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt::Display,
+};
+
+use crate::template::memory::format_bytes;
+
+/// Whether allocations are counted, i.e. the `count-allocs` feature is enabled.
+pub const IS_ENABLED: bool = cfg!(all(feature = "count-allocs", not(feature = "dhat-heap")));
+
+/// Allocations of a thread. Reallocations count as allocations of their new size.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub bytes: u64,
+}
+
+thread_local! {
+    static STATS: Cell<AllocStats> = const {
+        Cell::new(AllocStats {
+            allocations: 0,
+            bytes: 0,
+        })
+    };
+}
+
+/// Record an allocation of `size` bytes on the current thread.
+fn record(size: usize) {
+    // the thread local is unavailable while a thread is torn down, these allocations are lost.
+    let _ = STATS.try_with(|stats| {
+        let current = stats.get();
+        stats.set(AllocStats {
+            allocations: current.allocations + 1,
+            bytes: current.bytes + size as u64,
+        });
+    });
+}
+
+/// The allocations of the current thread since it started.
+fn current() -> AllocStats {
+    STATS.try_with(Cell::get).unwrap_or_default()
+}
+
+/// Wraps the system allocator and counts its allocations.
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+/// Run `f` and count the allocations it makes on the current thread.
+/// Without the `count-allocs` feature, or together with `dhat-heap`, the counts are always zero.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    let before = current();
+    let result = f();
+    let after = current();
+
+    let stats = AllocStats {
+        allocations: after.allocations - before.allocations,
+        bytes: after.bytes - before.bytes,
+    };
+
+    (result, stats)
+}
+
+/// Like [`measure`], but refuses to run if allocations are not counted, so an assertion on the
+/// counts can't pass silently.
+#[cfg(feature = "count-allocs")]
+fn measure_counted<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    if !IS_ENABLED {
+        panic!("allocations are not counted together with the `dhat-heap` feature");
+    }
+    measure(f)
+}
+
+/// Run `f` and assert that it allocates fewer than `limit` times.
+///
+/// # Panics
+/// If `f` allocates `limit` times or more, or if allocations are not counted.
+#[cfg(feature = "count-allocs")]
+pub fn assert_allocations_below<T>(limit: u64, f: impl FnOnce() -> T) -> T {
+    let (result, stats) = measure_counted(f);
+    assert!(
+        stats.allocations < limit,
+        "expected fewer than {limit} allocations, got {stats}"
+    );
+    result
+}
+
+/// Run `f` and assert that it does not allocate.
+///
+/// # Panics
+/// If `f` allocates, or if allocations are not counted.
+#[cfg(feature = "count-allocs")]
+pub fn assert_no_allocations<T>(f: impl FnOnce() -> T) -> T {
+    let (result, stats) = measure_counted(f);
+    assert!(
+        stats.allocations == 0,
+        "expected no allocations, got {stats}"
+    );
+    result
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations ({})",
+            self.allocations,
+            format_bytes(self.bytes)
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(
+    feature = "test_lib",
+    feature = "count-allocs",
+    not(feature = "dhat-heap")
+))]
+mod tests {
+    use super::{assert_allocations_below, assert_no_allocations, measure};
+
+    #[test]
+    fn counts_allocations() {
+        let (v, stats) = measure(|| vec![1_u64, 2, 3]);
+        assert_eq!(v.len(), 3);
+        assert_eq!(stats.allocations, 1);
+        assert_eq!(stats.bytes, 24);
+    }
+
+    #[test]
+    fn counts_reallocations() {
+        let (_, stats) = measure(|| {
+            let mut v: Vec<u8> = Vec::with_capacity(1);
+            v.extend([1, 2, 3, 4]);
+            v
+        });
+        assert_eq!(stats.allocations, 2);
+    }
+
+    #[test]
+    fn nests_measurements() {
+        let (inner, outer) = measure(|| measure(|| String::from("a")).1);
+        assert_eq!(inner.allocations, 1);
+        assert_eq!(outer.allocations, 1);
+    }
+
+    #[test]
+    fn passes_without_allocations() {
+        assert_eq!(assert_no_allocations(|| 1 + 1), 2);
+        assert_eq!(assert_allocations_below(2, || vec![1]).len(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_for_allocations() {
+        assert_no_allocations(|| Box::new(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn fails_for_exceeded_budgets() {
+        assert_allocations_below(1, || vec![1]);
+    }
+}
@@ -0,0 +1,318 @@
+//! Typed puzzle answers, normalized for submission and verification.
+use std::fmt::Display;
+
+/// The answer of a puzzle part.
+///
+/// Solutions can return any type that converts into an [`Answer`]: all primitive integers,
+/// strings and rendered grids. A string that spans several lines is a grid, e.g. the block
+/// letters that some puzzles ask to read off a screen. Grids of block letters are recognized and
+/// submitted as the letters they spell.
+///
+/// ```
+/// # use advent_of_code::template::Answer;
+/// let answer = Answer::from(".##.\n#..#\n#..#\n####\n#..#\n#..#");
+/// assert_eq!(answer.to_submission(), Some("A".into()));
+/// assert!(answer.matches("A"));
+/// assert!(Answer::from(42_u64).matches("42"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Signed(i128),
+    Unsigned(u128),
+    /// An integer of arbitrary size, as decimal digits with an optional leading `-`.
+    BigInt(String),
+    Text(String),
+    /// The rows of a rendered grid.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// An integer of arbitrary size, e.g. from a big number library.
+    /// Returns `None` if `digits` is not a decimal integer.
+    pub fn big_int(digits: &str) -> Option<Self> {
+        normalize_integer(digits).map(Answer::BigInt)
+    }
+
+    /// A rendered grid, where `lit` marks the cells that are on.
+    pub fn grid<R: AsRef<[bool]>>(rows: impl IntoIterator<Item = R>) -> Self {
+        Answer::Grid(
+            rows.into_iter()
+                .map(|row| {
+                    row.as_ref()
+                        .iter()
+                        .map(|&lit| if lit { '#' } else { '.' })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    /// Parse an answer from its string form, e.g. as stored in a report.
+    pub fn parse(s: &str) -> Self {
+        let s = s.trim_end_matches(['\r', '\n']);
+
+        if s.contains('\n') {
+            return Answer::Grid(s.lines().map(ToString::to_string).collect());
+        }
+
+        let s = s.trim();
+        if let Ok(x) = s.parse::<u128>() {
+            Answer::Unsigned(x)
+        } else if let Ok(x) = s.parse::<i128>() {
+            Answer::Signed(x)
+        } else if let Some(x) = normalize_integer(s) {
+            Answer::BigInt(x)
+        } else {
+            Answer::Text(s.to_string())
+        }
+    }
+
+    /// Returns `true` if the answer is rendered on several lines.
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Grid(_))
+    }
+
+    /// The answer in the form the website accepts, e.g. the letters of a grid of block letters.
+    /// Returns `None` for grids that are not recognized as letters.
+    pub fn to_submission(&self) -> Option<String> {
+        match self {
+            Answer::Signed(x) => Some(x.to_string()),
+            Answer::Unsigned(x) => Some(x.to_string()),
+            Answer::BigInt(x) => Some(x.clone()),
+            Answer::Text(x) => Some(x.trim().to_string()),
+            Answer::Grid(rows) => ocr(rows),
+        }
+    }
+
+    /// Returns `true` if this answer equals an expected answer in string form.
+    /// Integers are compared by value, text ignoring surrounding whitespace and grids either by
+    /// the letters they spell or cell by cell.
+    pub fn matches(&self, expected: &str) -> bool {
+        let expected = Answer::parse(expected);
+
+        match (self, &expected) {
+            (Answer::Grid(a), Answer::Grid(b)) => lit_cells(a) == lit_cells(b),
+            _ => self.to_submission().is_some() && self.to_submission() == expected.to_submission(),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Signed(x) => write!(f, "{x}"),
+            Answer::Unsigned(x) => write!(f, "{x}"),
+            Answer::BigInt(x) | Answer::Text(x) => write!(f, "{x}"),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+macro_rules! from_integer {
+    ($variant:ident, $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::$variant(value as _)
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(Signed, i8, i16, i32, i64, i128, isize);
+from_integer!(Unsigned, u8, u16, u32, u64, u128, usize);
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        if value.trim_end_matches(['\r', '\n']).contains('\n') {
+            Answer::parse(value)
+        } else {
+            Answer::Text(value.to_string())
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::from(value.as_str())
+    }
+}
+
+impl From<char> for Answer {
+    fn from(value: char) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Normalize a decimal integer of arbitrary size, e.g. `+007` to `7` and `-0` to `0`.
+fn normalize_integer(s: &str) -> Option<String> {
+    let s = s.trim();
+    let (is_negative, digits) = match s.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let digits = digits.trim_start_matches('0');
+    Some(match (is_negative, digits.is_empty()) {
+        (_, true) => "0".into(),
+        (true, false) => format!("-{digits}"),
+        (false, false) => digits.into(),
+    })
+}
+
+/// The lit cells of a grid, ignoring trailing unlit cells and surrounding blank rows.
+fn lit_cells(rows: &[String]) -> Vec<Vec<bool>> {
+    let mut cells: Vec<Vec<bool>> = rows
+        .iter()
+        .map(|row| {
+            let mut row: Vec<bool> = row.chars().map(|c| c == '#').collect();
+            while row.last() == Some(&false) {
+                row.pop();
+            }
+            row
+        })
+        .collect();
+
+    while cells.last().is_some_and(Vec::is_empty) {
+        cells.pop();
+    }
+    while cells.first().is_some_and(Vec::is_empty) {
+        cells.remove(0);
+    }
+
+    cells
+}
+
+/// Block letters, 6 cells high, as rendered by several puzzles.
+const LETTERS: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// Read the block letters of a grid. Letters are separated by at least one unlit column.
+/// Returns `None` if the grid is not 6 rows high or contains an unknown letter.
+fn ocr(rows: &[String]) -> Option<String> {
+    let cells = lit_cells(rows);
+    if cells.len() != 6 {
+        return None;
+    }
+
+    let width = cells.iter().map(Vec::len).max()?;
+    let is_lit = |x: usize, y: usize| cells[y].get(x).copied().unwrap_or(false);
+    let is_blank_column = |x: usize| (0..cells.len()).all(|y| !is_lit(x, y));
+
+    let mut letters = String::new();
+    let mut x = 0;
+
+    while x < width {
+        if is_blank_column(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && !is_blank_column(x) {
+            x += 1;
+        }
+
+        let glyph: Vec<String> = (0..cells.len())
+            .map(|y| {
+                (start..x)
+                    .map(|x| if is_lit(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        let glyph = glyph.join("\n");
+
+        let (letter, _) = LETTERS.iter().find(|(_, shape)| *shape == glyph)?;
+        letters.push(*letter);
+    }
+
+    Some(letters)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answer;
+
+    const HELLO: &str = "\
+#..#.####.#....#.....##.
+#..#.#....#....#....#..#
+####.###..#....#....#..#
+#..#.#....#....#....#..#
+#..#.#....#....#....#..#
+#..#.####.####.####..##.";
+
+    #[test]
+    fn converts_integers() {
+        assert_eq!(Answer::from(42_u32), Answer::Unsigned(42));
+        assert_eq!(Answer::from(-3_i64), Answer::Signed(-3));
+        assert_eq!(Answer::from(usize::MAX).to_string(), usize::MAX.to_string());
+    }
+
+    #[test]
+    fn normalizes_big_integers() {
+        assert_eq!(
+            Answer::big_int("+000123456789012345678901234567890123456789"),
+            Some(Answer::BigInt(
+                "123456789012345678901234567890123456789".into()
+            ))
+        );
+        assert_eq!(Answer::big_int("-0"), Some(Answer::BigInt("0".into())));
+        assert_eq!(Answer::big_int("12a"), None);
+    }
+
+    #[test]
+    fn reads_block_letters() {
+        let answer = Answer::from(HELLO);
+        assert!(answer.is_multiline());
+        assert_eq!(answer.to_submission(), Some("HELLO".into()));
+
+        let unknown = Answer::from("#.#\n.#.\n#.#");
+        assert_eq!(unknown.to_submission(), None);
+    }
+
+    #[test]
+    fn renders_grids() {
+        let answer = Answer::grid([[true, false], [false, true]]);
+        assert_eq!(answer.to_string(), "#.\n.#");
+    }
+
+    #[test]
+    fn matches_expected_answers() {
+        assert!(Answer::from(42_u64).matches(" 42\n"));
+        assert!(Answer::from(42_u64).matches("+042"));
+        assert!(!Answer::from(42_u64).matches("43"));
+        assert!(Answer::from(-1_i32).matches("-1"));
+        assert!(Answer::from(" abc ").matches("abc"));
+        assert!(Answer::from(HELLO).matches("HELLO"));
+        assert!(Answer::from(HELLO).matches(&HELLO.replace('.', " ")));
+        assert!(Answer::from("#.#\n.#.").matches("#.#\n.#.\n"));
+        assert!(!Answer::from("#.#\n.#.").matches("#.#"));
+    }
+}
@@ -0,0 +1,289 @@
+//! Confirmed answers of each day, used to verify solutions with `cargo all --verify`.
+use std::{collections::HashMap, fmt::Display, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{Answer, Day, Year};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Path of the answers file of a year, e.g. `data/2023/answers.json`.
+fn get_file_path(year: Year) -> String {
+    format!("{}/{ANSWERS_FILE_NAME}", year.data_dir())
+}
+
+/// Represents the confirmed answers for a single day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayAnswers {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Represents the confirmed answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<DayAnswers>,
+}
+
+/// Result of comparing an actual answer with the confirmed answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong {
+        expected: String,
+        actual: String,
+    },
+    /// No confirmed answer is known.
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "✔ correct"),
+            Verdict::Wrong { expected, actual } => {
+                write!(f, "✘ wrong (expected `{expected}`, got `{actual}`)")
+            }
+            Verdict::Unknown => write!(f, "? unknown"),
+        }
+    }
+}
+
+impl Answers {
+    /// Dehydrate answers to the JSON file of a year.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        fs::create_dir_all(year.data_dir())?;
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(get_file_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from the JSON file of a year. If not present, returns empty answers.
+    pub fn read_from_file(year: Year) -> Self {
+        let s = fs::read_to_string(get_file_path(year))
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from);
+
+        match s {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{}", e);
+                Answers::default()
+            }
+        }
+    }
+
+    /// Returns the confirmed answer for a part, if any.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let answers = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            1 => answers.part_1.as_deref(),
+            2 => answers.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Record `answer` as the confirmed answer for a part, replacing any previous answer.
+    pub fn set(&mut self, day: Day, part: u8, answer: &str) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(DayAnswers {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        let answers = &mut self.data[index];
+        match part {
+            1 => answers.part_1 = Some(answer.to_string()),
+            2 => answers.part_2 = Some(answer.to_string()),
+            _ => {}
+        }
+    }
+
+    /// Compare the answer a part produced with its confirmed answer.
+    pub fn verify(&self, day: Day, part: u8, actual: Option<&str>) -> Verdict {
+        match (self.get(day, part), actual) {
+            (Some(expected), Some(actual)) if Answer::parse(actual).matches(expected) => {
+                Verdict::Correct
+            }
+            (Some(expected), actual) => Verdict::Wrong {
+                expected: expected.to_string(),
+                actual: actual.unwrap_or("no answer").to_string(),
+            },
+            (None, _) => Verdict::Unknown,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(DayAnswers::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&DayAnswers> for JsonValue {
+    fn from(value: &DayAnswers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        map.insert(
+            "part_1".into(),
+            match value.part_1.clone() {
+                Some(x) => JsonValue::String(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "part_2".into(),
+            match value.part_2.clone() {
+                Some(x) => JsonValue::String(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for DayAnswers {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answers to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answers.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answers.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answers.part_2 to be null or string.")?;
+
+        Ok(DayAnswers {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, DayAnswers, Verdict};
+    use crate::day;
+
+    fn get_mock_answers() -> Answers {
+        Answers {
+            data: vec![DayAnswers {
+                day: day!(5),
+                part_1: Some("35".into()),
+                part_2: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn verifies_answers() {
+        let answers = get_mock_answers();
+        assert_eq!(answers.verify(day!(5), 1, Some("35")), Verdict::Correct);
+        assert_eq!(answers.verify(day!(5), 1, Some("035\n")), Verdict::Correct);
+        assert_eq!(
+            answers.verify(day!(5), 1, Some("36")),
+            Verdict::Wrong {
+                expected: "35".into(),
+                actual: "36".into()
+            }
+        );
+        assert_eq!(
+            answers.verify(day!(5), 1, None),
+            Verdict::Wrong {
+                expected: "35".into(),
+                actual: "no answer".into()
+            }
+        );
+        assert_eq!(answers.verify(day!(5), 2, None), Verdict::Unknown);
+        assert_eq!(answers.verify(day!(5), 2, Some("46")), Verdict::Unknown);
+        assert_eq!(answers.verify(day!(6), 1, Some("1")), Verdict::Unknown);
+    }
+
+    #[test]
+    fn sets_answers() {
+        let mut answers = get_mock_answers();
+        answers.set(day!(5), 2, "46");
+        answers.set(day!(1), 1, "142");
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(1));
+        assert_eq!(answers.get(day!(5), 1), Some("35"));
+        assert_eq!(answers.get(day!(5), 2), Some("46"));
+        assert_eq!(answers.get(day!(1), 1), Some("142"));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let json = r#"{ "data": [{ "day": "05", "part_1": "35", "part_2": null }] }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.data, get_mock_answers().data);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "26" }] }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+}
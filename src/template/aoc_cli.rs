@@ -1,10 +1,11 @@
-/// Wrapper module around the "aoc-cli" command-line.
+//! Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
+    io::{self, Write},
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::PuzzleId;
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -33,8 +34,8 @@ pub fn check() -> Result<(), AocCommandError> {
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+pub fn read(puzzle: PuzzleId) -> Result<Output, AocCommandError> {
+    let puzzle_path = puzzle.puzzle_path();
 
     let args = build_args(
         "read",
@@ -43,15 +44,15 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
             "--puzzle-file".into(),
             puzzle_path,
         ],
-        day,
+        puzzle,
     );
 
     call_aoc_cli(&args)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+pub fn download(puzzle: PuzzleId) -> Result<Output, AocCommandError> {
+    let input_path = puzzle.input_path();
+    let puzzle_path = puzzle.puzzle_path();
 
     let args = build_args(
         "download",
@@ -62,7 +63,7 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
             "--puzzle-file".into(),
             puzzle_path.to_string(),
         ],
-        day,
+        puzzle,
     );
 
     let output = call_aoc_cli(&args)?;
@@ -72,38 +73,26 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+pub fn submit(puzzle: PuzzleId, part: u8, result: &str) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
+    let mut args = build_args("submit", &[], puzzle);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
-}
-
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    // the output is captured so callers can interpret the server's verdict.
+    call_aoc_cli_captured(&args)
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
-}
-
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
-}
-
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+fn build_args(command: &str, args: &[String], puzzle: PuzzleId) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
-    }
+    cmd_args.push("--year".into());
+    cmd_args.push(puzzle.year.to_string());
 
-    cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
+    cmd_args.append(&mut vec![
+        "--day".into(),
+        puzzle.day.to_string(),
+        command.into(),
+    ]);
 
     cmd_args
 }
@@ -123,3 +112,19 @@ fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
         Err(AocCommandError::BadExitStatus(output))
     }
 }
+
+fn call_aoc_cli_captured(args: &[String]) -> Result<Output, AocCommandError> {
+    let output = Command::new("aoc")
+        .args(args)
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    let _ = io::stdout().write_all(&output.stdout);
+    let _ = io::stderr().write_all(&output.stderr);
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
+}
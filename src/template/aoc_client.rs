@@ -0,0 +1,414 @@
+//! Built-in client for the Advent of Code website.
+//!
+//! Reads the session cookie from the `AOC_SESSION` environment variable or from
+//! `~/.adventofcode.session`. The base URL defaults to
+//! `https://adventofcode.com` and can be overridden via `AOC_BASE_URL`, e.g. to run against a
+//! local stand-in server. Requests identify themselves with `AOC_USER_AGENT`, see
+//! [`user_agent`].
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::{submissions::ServerVerdict, PuzzleId};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    MissingSession,
+    Http(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or create `~/.adventofcode.session`."
+            ),
+            AocClientError::Http(e) => write!(f, "request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        AocClientError::Http(e.to_string())
+    }
+}
+
+/// Backend used to talk to the Advent of Code website.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    /// The built-in HTTP client.
+    Native,
+    /// The external `aoc` command-line, selected with `AOC_BACKEND=aoc-cli`.
+    AocCli,
+}
+
+impl Backend {
+    pub fn from_env() -> Self {
+        match env::var("AOC_BACKEND") {
+            Ok(x) if x == "aoc-cli" => Backend::AocCli,
+            _ => Backend::Native,
+        }
+    }
+}
+
+/// Server response to a submitted answer.
+#[derive(Debug)]
+pub struct SubmitResponse {
+    pub verdict: ServerVerdict,
+    /// The server's message, converted to plain text.
+    pub message: String,
+}
+
+/// The user agent of all requests. Advent of Code asks automated tools to identify themselves
+/// with a way to contact their author, so `AOC_USER_AGENT` should name your repository or email
+/// address. Defaults to the package name and version, with the `repository` of `Cargo.toml`.
+pub fn user_agent() -> String {
+    env::var("AOC_USER_AGENT").unwrap_or_else(|_| {
+        let name = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+        match env!("CARGO_PKG_REPOSITORY") {
+            "" => name.to_string(),
+            repository => format!("{name} (+{repository})"),
+        }
+    })
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, user_agent: &str) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(user_agent)
+                .build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    /// Creates a client configured from the environment.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+
+        let session = match env::var("AOC_SESSION") {
+            Ok(session) => session,
+            Err(_) => session_file_path()
+                .and_then(|path| fs::read_to_string(path).ok())
+                .ok_or(AocClientError::MissingSession)?,
+        };
+
+        if session.trim().is_empty() {
+            return Err(AocClientError::MissingSession);
+        }
+
+        Ok(Self::new(&base_url, &session, &user_agent()))
+    }
+
+    /// Fetch the puzzle input for a day.
+    pub fn get_input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.day_url(puzzle));
+        self.get(&url)
+    }
+
+    /// Fetch the puzzle description for a day, converted to markdown.
+    pub fn get_puzzle(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let html = self.get(&self.day_url(puzzle))?;
+        Ok(html_to_markdown(&html))
+    }
+
+    /// Submit an answer for one part of a day and interpret the server's verdict.
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmitResponse, AocClientError> {
+        let url = format!("{}/answer", self.day_url(puzzle));
+        let html = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer.trim())])?
+            .into_string()?;
+
+        let message = html_to_markdown(&html);
+        Ok(SubmitResponse {
+            verdict: ServerVerdict::from_response(&message),
+            message,
+        })
+    }
+
+    fn day_url(&self, puzzle: PuzzleId) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        Ok(self
+            .agent
+            .get(url)
+            .set("Cookie", &self.cookie())
+            .call()?
+            .into_string()?)
+    }
+}
+
+/// Download the input and puzzle description of a day to the data folder.
+pub fn download(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let input_path = puzzle.input_path();
+    let puzzle_path = puzzle.puzzle_path();
+
+    create_parent_dir(&input_path)?;
+    fs::write(&input_path, client.get_input(puzzle)?)?;
+    create_parent_dir(&puzzle_path)?;
+    fs::write(&puzzle_path, client.get_puzzle(puzzle)?)?;
+
+    println!("---");
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Fetch the puzzle description of a day, store it in the data folder and print it.
+pub fn read(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let description = client.get_puzzle(puzzle)?;
+    let puzzle_path = puzzle.puzzle_path();
+
+    create_parent_dir(&puzzle_path)?;
+    fs::write(&puzzle_path, &description)?;
+    println!("{description}");
+    Ok(())
+}
+
+fn create_parent_dir(path: &str) -> Result<(), io::Error> {
+    match Path::new(path).parent() {
+        Some(dir) => fs::create_dir_all(dir),
+        None => Ok(()),
+    }
+}
+
+fn session_file_path() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".adventofcode.session"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Convert the `<article>` elements of an Advent of Code page to markdown.
+/// Falls back to the whole document if the page has no articles.
+pub fn html_to_markdown(html: &str) -> String {
+    let articles: Vec<&str> = html
+        .split("<article")
+        .skip(1)
+        .filter_map(|s| s.split_once('>').map(|(_, rest)| rest))
+        .filter_map(|s| s.split("</article>").next())
+        .collect();
+
+    let content = if articles.is_empty() {
+        html.to_string()
+    } else {
+        articles.join("\n")
+    };
+
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut rest = content.as_str();
+
+    while let Some(start) = rest.find('<') {
+        let text = &rest[..start];
+        // skip formatting whitespace between block elements.
+        if in_pre || !(text.trim().is_empty() && out.ends_with('\n')) {
+            out.push_str(&decode_entities(text));
+        }
+        let Some(end) = rest[start..].find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let name = tag
+            .trim_start_matches('/')
+            .split_whitespace()
+            .next()
+            .unwrap_or("")
+            .to_lowercase();
+        let is_closing = tag.starts_with('/');
+
+        match (name.as_str(), is_closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            ("em", _) if !in_pre => out.push('*'),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            _ => {}
+        }
+    }
+
+    out.push_str(&decode_entities(rest));
+    out.trim().to_string() + "\n"
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    use super::{html_to_markdown, AocClient};
+    use crate::{day, template::submissions::ServerVerdict, template::PuzzleId, year};
+
+    /// Serve a single HTTP request with `body` and send the raw request to the returned receiver.
+    fn serve_once(body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut payload = vec![0; content_length];
+            reader.read_exact(&mut payload).unwrap();
+            request.push_str(&String::from_utf8(payload).unwrap());
+
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            tx.send(request).unwrap();
+        });
+
+        (base_url, rx)
+    }
+
+    #[test]
+    fn fetches_input() {
+        let (base_url, rx) = serve_once("1 2 3\n");
+        let client = AocClient::new(&base_url, "abc\n", "aoc-tests (test@example.com)");
+        let puzzle = PuzzleId::new(year!(2023), day!(5));
+        assert_eq!(client.get_input(puzzle).unwrap(), "1 2 3\n");
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("GET /2023/day/5/input "));
+        assert!(request.contains("session=abc\r\n"));
+        assert!(request
+            .to_lowercase()
+            .contains("user-agent: aoc-tests (test@example.com)\r\n"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, rx) = serve_once(
+            "<main><article><p>That's not the right answer; your answer is too low.</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "abc", "aoc-tests");
+        let puzzle = PuzzleId::new(year!(2023), day!(1));
+        let response = client.submit(puzzle, 2, "42").unwrap();
+        assert_eq!(response.verdict, ServerVerdict::TooLow);
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("POST /2023/day/1/answer "));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn converts_articles_to_markdown() {
+        let html = r#"<html><article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>For example:</p>
+<pre><code>1abc2
+<em>pqr3stu8vwx</em>
+</code></pre><p>Adding these together produces <code><em>142</em></code> &amp; more.</p><ul><li>one</li></ul></article></html>"#;
+
+        let expected = [
+            "## --- Day 1: Trebuchet?! ---",
+            "",
+            "For example:",
+            "",
+            "```",
+            "1abc2",
+            "pqr3stu8vwx",
+            "```",
+            "",
+            "Adding these together produces `*142*` & more.",
+            "",
+            "- one",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(html_to_markdown(html), expected);
+    }
+}
@@ -1,5 +1,126 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool, is_timed: bool) {
-    run_multi(all_days().collect(), is_release, is_timed);
+use crate::template::answers::{Answers, Verdict};
+use crate::template::registry::Registry;
+use crate::template::report::{Stage, Status};
+use crate::template::run_inputs::run_inputs;
+use crate::template::{all_days, run_multi::run_multi, PuzzleId, Year, ANSI_BOLD, ANSI_RESET};
+
+pub fn handle(
+    registry: &Registry,
+    year: Year,
+    is_release: bool,
+    is_timed: bool,
+    verify: bool,
+    accept: bool,
+) {
+    let run = run_multi(registry, year, all_days().collect(), is_release, is_timed);
+
+    if !verify && !accept {
+        return;
+    }
+
+    let mut answers = Answers::read_from_file(year);
+    let mut wrong = 0;
+    let mut accepted = 0;
+
+    println!();
+    println!("{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!("------");
+
+    for report in &run.reports {
+        let Stage::Part(part) = report.stage else {
+            continue;
+        };
+
+        let answer = match report.status {
+            Status::Solved => report.answer.as_deref(),
+            Status::Unsolved | Status::Panicked(_) => None,
+        };
+
+        let verdict = answers.verify(report.day, part, answer);
+
+        match (&verdict, answer) {
+            (Verdict::Wrong { .. }, _) => wrong += 1,
+            (Verdict::Unknown, Some(answer)) if accept => {
+                answers.set(report.day, part, answer);
+                accepted += 1;
+                println!("Day {} Part {part}: + accepted `{answer}`", report.day);
+                continue;
+            }
+            _ => {}
+        }
+
+        println!("Day {} Part {part}: {verdict}", report.day);
+    }
+
+    // parts with a confirmed answer but without a report, e.g. because their day failed to run.
+    for day_answers in &answers.data {
+        for part in [1, 2] {
+            let is_reported = run
+                .reports
+                .iter()
+                .any(|r| r.day == day_answers.day && r.stage == Stage::Part(part));
+
+            if !is_reported && answers.get(day_answers.day, part).is_some() {
+                wrong += 1;
+                let verdict = answers.verify(day_answers.day, part, None);
+                println!("Day {} Part {part}: {verdict}", day_answers.day);
+            }
+        }
+    }
+
+    if accepted > 0 {
+        match answers.store_file(year) {
+            Ok(()) => println!("Stored {accepted} accepted answer(s)."),
+            Err(e) => {
+                eprintln!("Failed to store answers: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    if verify && wrong > 0 {
+        eprintln!("{wrong} answer(s) did not match the confirmed answers.");
+        process::exit(1);
+    }
+}
+
+/// Run every solution against all inputs in its inputs directory and exit with a non-zero
+/// status if any input fails.
+pub fn handle_inputs(year: Year, is_release: bool) {
+    let mut inputs = 0;
+    let mut failed: Vec<String> = vec![];
+
+    for day in all_days() {
+        let puzzle = PuzzleId::new(year, day);
+
+        if day > 1 {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        match run_inputs(puzzle, is_release) {
+            Ok(run) if run.inputs == 0 => println!("No inputs."),
+            Ok(run) => {
+                inputs += run.inputs;
+                failed.extend(run.failed.iter().map(|name| format!("{puzzle}/{name}")));
+            }
+            Err(e) => {
+                eprintln!("Failed to run inputs of {puzzle}: {e}");
+                failed.push(puzzle.to_string());
+            }
+        }
+    }
+
+    println!();
+
+    if !failed.is_empty() {
+        eprintln!("{} input(s) failed: {}", failed.len(), failed.join(", "));
+        process::exit(1);
+    }
+
+    println!("All {inputs} input(s) passed.");
 }
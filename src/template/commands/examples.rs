@@ -0,0 +1,89 @@
+use std::{fs, path::Path, process};
+
+use crate::template::examples::extract;
+use crate::template::manifest::{Manifest, ManifestEntry};
+use crate::template::PuzzleId;
+
+/// Extract the examples and their answers from the downloaded puzzle description into the
+/// example files and the example manifest of a day.
+///
+/// Example files that are not empty and manifests that already list expected answers are only
+/// replaced with `overwrite`.
+pub fn handle(puzzle: PuzzleId, overwrite: bool) {
+    let puzzle_path = puzzle.puzzle_path();
+
+    let Ok(markdown) = fs::read_to_string(&puzzle_path) else {
+        eprintln!("Could not read puzzle description \"{puzzle_path}\". Try running `cargo download {}` first.", puzzle.day);
+        process::exit(1);
+    };
+
+    let examples = extract(&markdown);
+
+    if examples.is_empty() {
+        eprintln!("No examples found in \"{puzzle_path}\".");
+        process::exit(1);
+    }
+
+    let manifest_path = puzzle.example_manifest_path();
+    let existing = match Manifest::read_from_file(Path::new(&manifest_path)) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            eprintln!("Failed to read example manifest \"{manifest_path}\": {e}");
+            process::exit(1);
+        }
+    };
+
+    if existing.has_answers() && !overwrite {
+        eprintln!("Example manifest \"{manifest_path}\" already lists expected answers. Pass `--overwrite` to replace it.");
+        process::exit(1);
+    }
+
+    // the first example uses the default example file, further ones get a numbered suffix.
+    let files: Vec<(String, String)> = (0..examples.len())
+        .map(|i| {
+            let file_name = match i {
+                0 => format!("{}.txt", puzzle.day),
+                _ => format!("{}-{}.txt", puzzle.day, i + 1),
+            };
+            let path = format!("{}/examples/{file_name}", puzzle.year.data_dir());
+            (file_name, path)
+        })
+        .collect();
+
+    // check every file before writing any, so a refused run leaves no partial state behind.
+    for (_, path) in &files {
+        let is_empty = fs::read_to_string(path).map_or(true, |s| s.trim().is_empty());
+        if !is_empty && !overwrite {
+            eprintln!("Example file \"{path}\" is not empty. Pass `--overwrite` to replace it.");
+            process::exit(1);
+        }
+    }
+
+    let mut manifest = Manifest::default();
+
+    for (example, (file_name, path)) in examples.into_iter().zip(files) {
+        if let Err(e) = fs::write(&path, &example.input) {
+            eprintln!("Failed to write example file \"{path}\": {e}");
+            process::exit(1);
+        }
+
+        println!(
+            "Wrote example to \"{path}\" (part 1: {}, part 2: {})",
+            example.part_1.as_deref().unwrap_or("-"),
+            example.part_2.as_deref().unwrap_or("-")
+        );
+
+        manifest.data.push(ManifestEntry {
+            input: file_name,
+            part_1: example.part_1,
+            part_2: example.part_2,
+        });
+    }
+
+    if let Err(e) = manifest.store_file(Path::new(&manifest_path)) {
+        eprintln!("Failed to write example manifest \"{manifest_path}\": {e}");
+        process::exit(1);
+    }
+
+    println!("Wrote expected answers to \"{manifest_path}\"");
+}
@@ -1,5 +1,6 @@
 pub mod all;
 pub mod download;
+pub mod examples;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
@@ -1,14 +1,23 @@
 use std::process;
 
-use crate::template::{aoc_cli, Day};
+use crate::template::aoc_client::{self, Backend};
+use crate::template::{aoc_cli, PuzzleId};
+
+pub fn handle(puzzle: PuzzleId) {
+    if Backend::from_env() == Backend::Native {
+        if let Err(e) = aoc_client::read(puzzle) {
+            eprintln!("failed to read {puzzle}: {e}");
+            process::exit(1);
+        }
+        return;
+    }
 
-pub fn handle(day: Day) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::read(day) {
+    if let Err(e) = aoc_cli::read(puzzle) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
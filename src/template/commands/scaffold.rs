@@ -1,26 +1,60 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
-    process,
+    path::Path,
+    process::{self, Command, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::templates::{self, Placeholders};
+use crate::template::PuzzleId;
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Lists the example file with an expected answer for each part.
+/// Fill in the answers from the puzzle description to have the tests check them.
+const MANIFEST_TEMPLATE: &str = r#"{
+  "data": [
+    { "input": "%EXAMPLE_FILE%", "part_1": null, "part_2": null }
+  ]
+}
+"#;
+
+fn create_parent_dir(path: &str) -> Result<(), std::io::Error> {
+    match Path::new(path).parent() {
+        Some(dir) => fs::create_dir_all(dir),
+        None => Ok(()),
+    }
+}
 
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+    create_parent_dir(path)?;
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    create_parent_dir(path)?;
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(puzzle: PuzzleId, template: Option<&str>, answer_type: Option<String>) {
+    let placeholders = Placeholders::new(puzzle, answer_type);
+    let (module, uses_parser) =
+        match templates::load(template.unwrap_or(templates::DEFAULT_TEMPLATE)) {
+            Ok(module) => (
+                templates::render(&module, &placeholders),
+                module.contains("%PARSER%"),
+            ),
+            Err(e) => {
+                eprintln!("Failed to load module template: {e}");
+                process::exit(1);
+            }
+        };
+
+    let input_path = puzzle.input_path();
+    let example_path = puzzle.example_path();
+    let module_path = puzzle.bin_path();
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -30,11 +64,7 @@ pub fn handle(day: Day) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(module.as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -44,7 +74,18 @@ pub fn handle(day: Day) {
         }
     }
 
+    if let Some(parser) = placeholders.parser.as_ref().filter(|_| uses_parser) {
+        println!("Generated parser for {}", parser.shape);
+        // the generated parser is not formatted, formatting is best-effort.
+        let _ = Command::new("rustfmt")
+            .args(["--edition", "2021", &module_path])
+            .stderr(Stdio::null())
+            .status();
+    }
+
     match create_file(&input_path) {
+        // keep quiet about an input that was already downloaded.
+        Ok(file) if file.metadata().is_ok_and(|m| m.len() > 0) => {}
         Ok(_) => {
             println!("Created empty input file \"{}\"", &input_path);
         }
@@ -64,6 +105,29 @@ pub fn handle(day: Day) {
         }
     }
 
+    let manifest_path = puzzle.example_manifest_path();
+
+    match safe_create_file(&manifest_path) {
+        Ok(mut file) => {
+            let manifest =
+                MANIFEST_TEMPLATE.replace("%EXAMPLE_FILE%", &format!("{}.txt", puzzle.day));
+            if let Err(e) = file.write_all(manifest.as_bytes()) {
+                eprintln!("Failed to write example manifest: {e}");
+                process::exit(1);
+            }
+            println!("Created example manifest \"{}\"", &manifest_path);
+        }
+        // keep a manifest that was already filled in.
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+        Err(e) => {
+            eprintln!("Failed to create example manifest: {e}");
+            process::exit(1);
+        }
+    }
+
     println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    println!(
+        "🎄 Type `cargo solve {} --year {}` to run your solution.",
+        puzzle.day, puzzle.year
+    );
 }
@@ -1,9 +1,26 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::run_inputs::{run_examples, run_inputs, InputsRun};
+use crate::template::run_multi::Error;
+use crate::template::runner::InputSource;
+use crate::template::watch::watch;
+use crate::template::PuzzleId;
 
-pub fn handle(day: Day, release: bool, time: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    time: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    force: bool,
+    input: &InputSource,
+) {
+    if submit_part.is_some() && !input.is_puzzle() {
+        eprintln!("`--submit` can only be used with the puzzle input.");
+        process::exit(1);
+    }
+
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
 
     if dhat {
         cmd_args.extend([
@@ -21,12 +38,18 @@ pub fn handle(day: Day, release: bool, time: bool, dhat: bool, submit_part: Opti
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
+
+        if force {
+            cmd_args.push("--force".to_string());
+        }
     }
 
     if time {
         cmd_args.push("--time".to_string());
     }
 
+    cmd_args.extend(input.to_args());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -36,3 +59,54 @@ pub fn handle(day: Day, release: bool, time: bool, dhat: bool, submit_part: Opti
 
     cmd.wait().unwrap();
 }
+
+/// Run a solution against every input in its inputs directory and exit with a non-zero status
+/// if any input fails.
+pub fn handle_inputs(puzzle: PuzzleId, release: bool) {
+    let empty = format!("No inputs found in \"{}\".", puzzle.inputs_dir());
+    exit_with_summary(puzzle, run_inputs(puzzle, release), &empty);
+}
+
+/// Run a solution against every example in its example manifest and exit with a non-zero
+/// status if any example fails.
+pub fn handle_examples(puzzle: PuzzleId, release: bool) {
+    let empty = format!(
+        "No examples listed in \"{}\".",
+        puzzle.example_manifest_path()
+    );
+    exit_with_summary(puzzle, run_examples(puzzle, release), &empty);
+}
+
+/// Re-run a solution against its examples and its input whenever its source or data changes.
+pub fn handle_watch(puzzle: PuzzleId, release: bool) {
+    watch(puzzle, release);
+}
+
+fn exit_with_summary(puzzle: PuzzleId, run: Result<InputsRun, Error>, empty: &str) {
+    let run = match run {
+        Ok(run) => run,
+        Err(e) => {
+            eprintln!("Failed to run {puzzle}: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!();
+
+    if run.inputs == 0 {
+        eprintln!("{empty}");
+        process::exit(1);
+    }
+
+    if !run.failed.is_empty() {
+        eprintln!(
+            "{} of {} input(s) failed: {}",
+            run.failed.len(),
+            run.inputs,
+            run.failed.join(", ")
+        );
+        process::exit(1);
+    }
+
+    println!("All {} input(s) passed.", run.inputs);
+}
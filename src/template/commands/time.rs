@@ -1,11 +1,20 @@
 use std::collections::HashSet;
+use std::path::Path;
+use std::process;
 
-use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::against::{self, format_stage, StageAgainst, Worktree};
+use crate::template::history::{compare, format_comparison, Comparison, RunEnvironment};
+use crate::template::memory::{format_bytes, MemoryStats};
+use crate::template::registry::Registry;
+use crate::template::run_multi::{child_commands, run_multi};
+use crate::template::stats::Change;
+use crate::template::timings::{StageTiming, Timings};
+use crate::template::{
+    all_days, readme_benchmarks, Day, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
-pub fn handle(day: Option<Day>, recreate_all: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(registry: &Registry, year: Year, day: Option<Day>, recreate_all: bool) {
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map(|day| HashSet::from([day])).unwrap_or_else(|| {
         if recreate_all {
@@ -18,18 +27,263 @@ pub fn handle(day: Option<Day>, recreate_all: bool) {
         }
     });
 
-    let timings = run_multi(days_to_run, true, true).unwrap();
+    let timings = run_multi(registry, year, days_to_run, true, true)
+        .timings
+        .unwrap();
+    print_summary(&timings);
 
-    let merged_timings = stored_timings.merge(&timings);
-    merged_timings.store_file().unwrap();
+    let merged_timings = stored_timings.record(&timings, RunEnvironment::capture());
+    merged_timings.store_file(year).unwrap();
+
+    // compare the days that were just benchmarked against their previous run.
+    let comparisons = compare(
+        &merged_timings.history,
+        timings.data.iter().map(|t| t.day),
+        None,
+    );
+    print_comparisons(&comparisons);
 
     println!();
-    match readme_benchmarks::update(merged_timings) {
+    match readme_benchmarks::update(year, merged_timings) {
         Ok(()) => {
             println!("Stored updated benchmarks.")
         }
-        Err(_) => {
-            eprintln!("Failed to store updated benchmarks.");
+        Err(e) => {
+            eprintln!("Failed to store updated benchmarks: {e}");
+        }
+    }
+}
+
+/// Print the parse and solve times of all days that were just benchmarked as a table.
+fn print_summary(timings: &Timings) {
+    if timings.data.is_empty() {
+        return;
+    }
+
+    println!();
+    println!(
+        "{ANSI_BOLD}{:<6} {:>12} {:>12} {:>12}{ANSI_RESET}",
+        "Day", "Parse", "Part 1", "Part 2"
+    );
+
+    let format = |stage: &Option<StageTiming>| {
+        stage
+            .as_ref()
+            .map_or_else(|| "-".into(), StageTiming::formatted)
+    };
+
+    for timing in &timings.data {
+        println!(
+            "{:<6} {:>12} {:>12} {:>12}",
+            timing.day.to_string(),
+            format(&timing.parse),
+            format(&timing.part_1),
+            format(&timing.part_2),
+        );
+    }
+}
+
+/// Profile the heap of `day`, or of all days without memory stats (all days if `recreate_all`),
+/// with dhat and store the peak heap usage and allocations of every day next to its timings.
+pub fn handle_memory(year: Year, day: Option<Day>, recreate_all: bool) {
+    let mut timings = Timings::read_from_file(year);
+
+    let days_to_run: Vec<Day> = day.map_or_else(
+        || {
+            all_days()
+                .filter(|day| {
+                    recreate_all
+                        || !timings
+                            .data
+                            .iter()
+                            .any(|t| &t.day == day && t.memory.is_some())
+                })
+                .collect()
+        },
+        |day| vec![day],
+    );
+
+    let mut profiled: Vec<(Day, MemoryStats)> = vec![];
+
+    for day in days_to_run {
+        let puzzle = PuzzleId::new(year, day);
+
+        // skip days that have not been scaffolded yet.
+        if !Path::new(&puzzle.bin_path()).exists() {
+            continue;
+        }
+
+        println!();
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        match child_commands::run_heap_profile(puzzle) {
+            Ok(memory) => {
+                timings = timings.with_memory(day, memory);
+                profiled.push((day, memory));
+            }
+            Err(e) => eprintln!("Failed to profile day {day}: {e}"),
+        }
+    }
+
+    if profiled.is_empty() {
+        println!("No days to profile.");
+        return;
+    }
+
+    println!();
+    println!(
+        "{ANSI_BOLD}{:<6} {:>12} {:>12} {:>12}{ANSI_RESET}",
+        "Day", "Peak Heap", "Allocated", "Allocations"
+    );
+    for (day, memory) in &profiled {
+        println!(
+            "{:<6} {:>12} {:>12} {:>12}",
+            day.to_string(),
+            format_bytes(memory.peak_bytes),
+            format_bytes(memory.total_bytes),
+            memory.allocations,
+        );
+    }
+
+    timings.store_file(year).unwrap();
+
+    println!();
+    match readme_benchmarks::update(year, timings) {
+        Ok(()) => {
+            println!("Stored updated memory stats.")
+        }
+        Err(e) => {
+            eprintln!("Failed to store updated memory stats: {e}");
         }
     }
 }
+
+/// Compare the latest benchmarks of every day against a baseline and exit with a non-zero status
+/// if any stage regressed. The baseline is the previous run of a day, or the latest run at the
+/// commit `baseline` if given.
+pub fn handle_compare(year: Year, baseline: Option<&str>) {
+    let timings = Timings::read_from_file(year);
+    let comparisons = compare(&timings.history, all_days(), baseline);
+
+    if comparisons.is_empty() {
+        match baseline {
+            Some(rev) => {
+                eprintln!("No benchmarks at a commit matching `{rev}` to compare against.")
+            }
+            None => eprintln!("No earlier benchmarks to compare against. Run `cargo time` first."),
+        }
+        process::exit(1);
+    }
+
+    print_comparisons(&comparisons);
+
+    let has_regressions = comparisons
+        .iter()
+        .flat_map(|c| &c.stages)
+        .any(|stage| matches!(stage.change, Change::Regression(_)));
+
+    if has_regressions {
+        process::exit(1);
+    }
+}
+
+/// Print comparisons against baseline runs as tables, one per pair of runs.
+fn print_comparisons(comparisons: &[Comparison]) {
+    for comparison in comparisons {
+        if comparison.stages.is_empty() {
+            continue;
+        }
+
+        println!();
+        println!(
+            "{ANSI_BOLD}Comparing {} → {}{ANSI_RESET}",
+            comparison.baseline, comparison.current
+        );
+
+        let (baseline, current) = (&comparison.baseline, &comparison.current);
+        if baseline.host != current.host || baseline.rustc != current.rustc {
+            println!(
+                "{ANSI_ITALIC}The runs used a different host or compiler, changes may not be caused by code.{ANSI_RESET}"
+            );
+        }
+
+        println!(
+            "{ANSI_BOLD}{:<6} {:<8} {:>12} {:>12}   Change{ANSI_RESET}",
+            "Day", "Stage", "Baseline", "Current"
+        );
+        comparison
+            .stages
+            .iter()
+            .for_each(|stage| println!("{}", format_comparison(stage)));
+    }
+
+    let stages = comparisons.iter().flat_map(|c| &c.stages);
+    let regressions = stages
+        .clone()
+        .filter(|s| matches!(s.change, Change::Regression(_)))
+        .count();
+    let improvements = stages
+        .filter(|s| matches!(s.change, Change::Improvement(_)))
+        .count();
+
+    if regressions + improvements > 0 {
+        println!();
+        println!("{regressions} regression(s), {improvements} improvement(s).");
+    }
+}
+
+/// Benchmark `day`, or all days, with the working tree and with the revision `rev`, and print the
+/// speedup of every stage. The results are not stored.
+pub fn handle_against(year: Year, day: Option<Day>, rev: &str) {
+    let worktree = match Worktree::create(rev) {
+        Ok(worktree) => worktree,
+        Err(e) => {
+            eprintln!("Failed to check out `{rev}`: {e}");
+            process::exit(1);
+        }
+    };
+
+    let days: Vec<Day> = day.map_or_else(|| all_days().collect(), |day| vec![day]);
+    let (days, current, baseline) = match against::build(year, &days, &worktree) {
+        Ok(builds) => builds,
+        Err(e) => {
+            eprintln!("Failed to build solutions: {e}");
+            process::exit(1);
+        }
+    };
+
+    let mut stages: Vec<StageAgainst> = vec![];
+
+    for day in days {
+        println!();
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        match against::bench_day(PuzzleId::new(year, day), &current, &baseline) {
+            Ok(day_stages) => stages.extend(day_stages),
+            Err(e) => eprintln!("Failed to benchmark day {day}: {e}"),
+        }
+    }
+
+    println!();
+    if stages.is_empty() {
+        println!(
+            "No days are solved in both the working tree and {}.",
+            worktree.commit
+        );
+        return;
+    }
+
+    println!(
+        "{ANSI_BOLD}Comparing {} ({rev}) → working tree{ANSI_RESET}",
+        worktree.commit
+    );
+    println!(
+        "{ANSI_BOLD}{:<6} {:<8} {:>12} {:>12}   Speedup{ANSI_RESET}",
+        "Day", "Stage", worktree.commit, "Current"
+    );
+    stages
+        .iter()
+        .for_each(|stage| println!("{}", format_stage(stage)));
+}
@@ -0,0 +1,206 @@
+//! Extracts example inputs and their answers from a puzzle description in markdown.
+//!
+//! Puzzle descriptions introduce example inputs with a sentence mentioning the "example",
+//! followed by a code block. The answer for the example is the last emphasized code span of the
+//! section, e.g. `` `*142*` ``. The description of part two starts with a `--- Part Two ---`
+//! heading, and reuses the example of part one if it does not introduce its own.
+
+/// An example input with the expected answer of each part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// A section of the puzzle description, i.e. the description of one part.
+#[derive(Debug, Default)]
+struct Section {
+    /// Code blocks that are introduced as an example.
+    examples: Vec<String>,
+    /// All code blocks, in case none is introduced as an example.
+    blocks: Vec<String>,
+    /// Text outside of code blocks.
+    text: String,
+}
+
+impl Section {
+    fn example(&self) -> Option<&String> {
+        self.examples.first().or(self.blocks.first())
+    }
+
+    /// The last emphasized code span, e.g. `` `*142*` `` or `` *`142`* ``.
+    fn answer(&self) -> Option<String> {
+        let candidates = [("`*", "*`"), ("*`", "`*")];
+
+        candidates
+            .iter()
+            .filter_map(|(open, close)| {
+                let start = self.text.rfind(open)?;
+                let rest = &self.text[start + open.len()..];
+                let end = rest.find(close)?;
+                Some((start, rest[..end].trim().to_string()))
+            })
+            .filter(|(_, answer)| !answer.is_empty() && !answer.contains('\n'))
+            .max_by_key(|(start, _)| *start)
+            .map(|(_, answer)| answer)
+    }
+}
+
+/// Split a puzzle description into sections, one per part.
+fn parse_sections(markdown: &str) -> Vec<Section> {
+    let mut sections = vec![Section::default()];
+    let mut block: Option<Vec<&str>> = None;
+    // the paragraph right before a code block decides whether it is an example.
+    let mut paragraph = String::new();
+    let mut is_new_paragraph = true;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            match block.take() {
+                Some(lines) => {
+                    let code = lines.join("\n") + "\n";
+                    let section = sections.last_mut().unwrap();
+                    if paragraph.to_lowercase().contains("example") {
+                        section.examples.push(code.clone());
+                    }
+                    section.blocks.push(code);
+                    paragraph.clear();
+                }
+                None => block = Some(vec![]),
+            }
+            continue;
+        }
+
+        if let Some(lines) = block.as_mut() {
+            lines.push(line);
+            continue;
+        }
+
+        if line.starts_with("## ") && line.contains("Part Two") {
+            sections.push(Section::default());
+            paragraph.clear();
+            continue;
+        }
+
+        let section = sections.last_mut().unwrap();
+        section.text.push_str(line);
+        section.text.push('\n');
+
+        if line.trim().is_empty() {
+            is_new_paragraph = true;
+        } else {
+            if is_new_paragraph {
+                paragraph.clear();
+                is_new_paragraph = false;
+            }
+            paragraph.push_str(line);
+            paragraph.push(' ');
+        }
+    }
+
+    sections
+}
+
+/// Extract the examples of both parts from a puzzle description.
+/// Returns one example if both parts share it, two if part two introduces its own.
+pub fn extract(markdown: &str) -> Vec<Example> {
+    let sections = parse_sections(markdown);
+
+    let Some(part_1) = sections.first() else {
+        return vec![];
+    };
+
+    let mut examples: Vec<Example> = vec![];
+
+    if let Some(input) = part_1.example() {
+        examples.push(Example {
+            input: input.clone(),
+            part_1: part_1.answer(),
+            part_2: None,
+        });
+    }
+
+    if let Some(part_2) = sections.get(1) {
+        let answer = part_2.answer();
+        match part_2.example() {
+            Some(input) if examples.iter().all(|e| e.input != *input) => examples.push(Example {
+                input: input.clone(),
+                part_1: None,
+                part_2: answer,
+            }),
+            _ => {
+                if let Some(example) = examples.first_mut() {
+                    example.part_2 = answer;
+                }
+            }
+        }
+    }
+
+    examples
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract, Example};
+
+    const PART_ONE: &str = r#"## --- Day 1: Trebuchet?! ---
+
+The newly-improved calibration document consists of lines of text; each line originally contained a specific *calibration value*.
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, the calibration values of these lines are `12` and `38`. Adding these together produces `*50*`.
+
+Consider your entire calibration document. *What is the sum of all of the calibration values?*
+"#;
+
+    #[test]
+    fn extracts_example_of_part_one() {
+        assert_eq!(
+            extract(PART_ONE),
+            vec![Example {
+                input: "1abc2\npqr3stu8vwx\n".into(),
+                part_1: Some("50".into()),
+                part_2: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn extracts_separate_example_of_part_two() {
+        let markdown = format!(
+            "{PART_ONE}\n## --- Part Two ---\n\nFor example:\n\n```\ntwo1nine\n```\n\nThis produces `*29*`.\n"
+        );
+        let examples = extract(&markdown);
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[1].input, "two1nine\n");
+        assert_eq!(examples[1].part_1, None);
+        assert_eq!(examples[1].part_2, Some("29".into()));
+    }
+
+    #[test]
+    fn reuses_example_for_part_two() {
+        let markdown = format!(
+            "{PART_ONE}\n## --- Part Two ---\n\nIn the above example, the answer is *`7`*.\n"
+        );
+        let examples = extract(&markdown);
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].part_1, Some("50".into()));
+        assert_eq!(examples[0].part_2, Some("7".into()));
+    }
+
+    #[test]
+    fn skips_code_blocks_that_are_not_examples() {
+        let markdown = "Consider this:\n\n```\nnot it\n```\n\nHere is an example:\n\n```\n1 2 3\n```\n\nThe result is `*6*`.\n";
+        let examples = extract(markdown);
+        assert_eq!(examples[0].input, "1 2 3\n");
+    }
+}
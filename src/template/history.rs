@@ -0,0 +1,400 @@
+//! History of benchmark runs, used to detect regressions.
+//!
+//! Every `cargo time` run is recorded in the timings file of a year together with the environment
+//! it ran in: the git commit, the date, the rust compiler and the host. A comparison matches the
+//! latest run of every day against an earlier run and tests each stage for a significant change.
+use std::{
+    collections::HashMap,
+    env, fs,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    stats::{format_nanos, BenchStats, Change},
+    timings::Timing,
+    Day,
+};
+
+/// The environment a benchmark run was executed in.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RunEnvironment {
+    /// Abbreviated hash of the checked out commit, if run in a git repository.
+    pub commit: Option<String>,
+    /// Whether the working tree had uncommitted changes.
+    pub dirty: bool,
+    /// Date of the run in UTC, e.g. `2023-12-05T18:30:00Z`.
+    pub date: String,
+    /// Output of `rustc --version`.
+    pub rustc: Option<String>,
+    /// Host name of the machine.
+    pub host: Option<String>,
+}
+
+/// A single `cargo time` run with the timings of the days it benchmarked.
+#[derive(Clone, Debug)]
+pub struct Run {
+    pub environment: RunEnvironment,
+    pub data: Vec<Timing>,
+}
+
+/// Comparison of the days that were benchmarked in one run against a baseline run.
+#[derive(Clone, Debug)]
+pub struct Comparison {
+    pub baseline: RunEnvironment,
+    pub current: RunEnvironment,
+    pub stages: Vec<StageComparison>,
+}
+
+/// Comparison of a stage of a day against a baseline run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StageComparison {
+    pub day: Day,
+    /// Name of the stage, e.g. `Part 1`.
+    pub stage: &'static str,
+    /// Median of the baseline and the current run, in nanoseconds.
+    pub baseline: f64,
+    pub current: f64,
+    pub change: Change,
+}
+
+/// Run a command and return its trimmed output, if it succeeds.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let output = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!output.is_empty()).then_some(output)
+}
+
+fn host_name() -> Option<String> {
+    env::var("HOSTNAME")
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .or_else(|| command_output("hostname", &[]))
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// Format seconds since the unix epoch as an ISO 8601 date in UTC.
+fn format_utc(secs: u64) -> String {
+    #[allow(clippy::cast_possible_wrap)]
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+
+    // civil date from days since the epoch, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+impl RunEnvironment {
+    /// Capture the environment of the current process.
+    pub fn capture() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        Self {
+            commit: command_output("git", &["rev-parse", "--short", "HEAD"]),
+            dirty: command_output("git", &["status", "--porcelain", "--untracked-files=no"])
+                .is_some(),
+            date: format_utc(secs),
+            rustc: command_output("rustc", &["--version"]),
+            host: host_name(),
+        }
+    }
+
+    /// Returns `true` if `rev` is a prefix of the commit of this run.
+    pub fn matches_commit(&self, rev: &str) -> bool {
+        self.commit
+            .as_deref()
+            .is_some_and(|commit| commit.starts_with(rev) || rev.starts_with(commit))
+    }
+}
+
+impl std::fmt::Display for RunEnvironment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let commit = self.commit.as_deref().unwrap_or("no commit");
+        let dirty = if self.dirty { " (modified)" } else { "" };
+        write!(f, "{commit}{dirty} on {}", self.date)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The stages of a timing with their statistics.
+fn stage_stats(timing: &Timing) -> [(&'static str, Option<&BenchStats>); 3] {
+    timing
+        .stages()
+        .map(|(name, stage)| (name, stage.and_then(|stage| stage.stats.as_ref())))
+}
+
+/// Returns the index and timing of the latest run in `runs` that benchmarked `day`.
+fn latest<'a>(
+    runs: impl DoubleEndedIterator<Item = (usize, &'a Run)>,
+    day: Day,
+) -> Option<(usize, &'a Timing)> {
+    runs.rev()
+        .find_map(|(i, run)| run.data.iter().find(|t| t.day == day).map(|t| (i, t)))
+}
+
+/// Compare the latest run of every day in `days` against a baseline run.
+///
+/// The baseline is the latest earlier run of the day, or the latest run at a commit starting with
+/// `baseline_rev`, if given. Days without a baseline are skipped.
+pub fn compare(
+    history: &[Run],
+    days: impl Iterator<Item = Day>,
+    baseline_rev: Option<&str>,
+) -> Vec<Comparison> {
+    let mut comparisons: Vec<Comparison> = vec![];
+
+    for day in days {
+        let Some((current_index, current)) = latest(history.iter().enumerate(), day) else {
+            continue;
+        };
+
+        let candidates = history
+            .iter()
+            .enumerate()
+            .filter(|(i, run)| match baseline_rev {
+                Some(rev) => *i != current_index && run.environment.matches_commit(rev),
+                None => *i < current_index,
+            });
+
+        let Some((baseline_index, baseline)) = latest(candidates, day) else {
+            continue;
+        };
+
+        // stages are only compared if both runs have statistics for them.
+        let stages: Vec<StageComparison> = stage_stats(current)
+            .into_iter()
+            .zip(stage_stats(baseline))
+            .filter_map(
+                |((stage, current), (_, baseline))| match (current, baseline) {
+                    (Some(current), Some(baseline)) => Some(StageComparison {
+                        day,
+                        stage,
+                        baseline: baseline.median,
+                        current: current.median,
+                        change: current.compare(baseline),
+                    }),
+                    _ => None,
+                },
+            )
+            .collect();
+
+        let current = &history[current_index].environment;
+        let baseline = &history[baseline_index].environment;
+
+        // days that were benchmarked together are listed together.
+        match comparisons
+            .iter_mut()
+            .find(|c| &c.baseline == baseline && &c.current == current)
+        {
+            Some(comparison) => comparison.stages.extend(stages),
+            None => comparisons.push(Comparison {
+                baseline: baseline.clone(),
+                current: current.clone(),
+                stages,
+            }),
+        }
+    }
+
+    comparisons
+}
+
+/// Format a comparison as a table row.
+pub fn format_comparison(comparison: &StageComparison) -> String {
+    format!(
+        "{:<6} {:<8} {:>12} {:>12}   {}",
+        comparison.day.to_string(),
+        comparison.stage,
+        format_nanos(comparison.baseline),
+        format_nanos(comparison.current),
+        comparison.change
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl RunEnvironment {
+    fn to_json_map(&self) -> HashMap<String, JsonValue> {
+        let optional = |x: &Option<String>| x.clone().map_or(JsonValue::Null, JsonValue::String);
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("commit".into(), optional(&self.commit));
+        map.insert("dirty".into(), JsonValue::Boolean(self.dirty));
+        map.insert("date".into(), JsonValue::String(self.date.clone()));
+        map.insert("rustc".into(), optional(&self.rustc));
+        map.insert("host".into(), optional(&self.host));
+        map
+    }
+}
+
+impl TryFrom<&JsonValue> for RunEnvironment {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected run to be a JSON object.")?;
+
+        let optional = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+
+        Ok(RunEnvironment {
+            commit: optional("commit"),
+            dirty: json
+                .get("dirty")
+                .and_then(|v| v.get::<bool>().copied())
+                .unwrap_or(false),
+            date: optional("date").ok_or("Expected run.date to be a string.")?,
+            rustc: optional("rustc"),
+            host: optional("host"),
+        })
+    }
+}
+
+impl From<&Run> for JsonValue {
+    fn from(value: &Run) -> Self {
+        // the environment is stored next to the timings of a run.
+        let mut map = value.environment.to_json_map();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Run {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let data = value
+            .get::<HashMap<String, JsonValue>>()
+            .and_then(|json| json.get("data"))
+            .and_then(|data| data.get::<Vec<JsonValue>>())
+            .ok_or("Expected run.data to be an array.")?;
+
+        Ok(Run {
+            environment: RunEnvironment::try_from(value)?,
+            data: data
+                .iter()
+                .map(Timing::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{compare, format_utc, Run, RunEnvironment};
+    use crate::day;
+    use crate::template::stats::{BenchStats, Change};
+    use crate::template::timings::{StageTiming, Timing};
+
+    fn environment(commit: &str) -> RunEnvironment {
+        RunEnvironment {
+            commit: Some(commit.into()),
+            dirty: false,
+            date: "2023-12-05T18:30:00Z".into(),
+            rustc: Some("rustc 1.74.0".into()),
+            host: Some("alice".into()),
+        }
+    }
+
+    fn run(commit: &str, nanos: &[u64]) -> Run {
+        let samples: Vec<Duration> = nanos.iter().map(|n| Duration::from_nanos(*n)).collect();
+        let stats = BenchStats::from_samples(&samples, 0);
+        Run {
+            environment: environment(commit),
+            data: vec![Timing {
+                part_1: Some(StageTiming {
+                    nanos: 1.0,
+                    samples: 1,
+                    stats,
+                }),
+                ..Timing::new(day!(5))
+            }],
+        }
+    }
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(format_utc(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_utc(1_701_801_000), "2023-12-05T18:30:00Z");
+        assert_eq!(format_utc(951_782_400), "2000-02-29T00:00:00Z");
+    }
+
+    #[test]
+    fn compares_against_previous_run() {
+        let history = vec![
+            run("aaaaaaa", &[100, 101, 99, 100, 102, 98]),
+            run("bbbbbbb", &[120, 121, 119, 120, 122, 118]),
+        ];
+
+        let comparisons = compare(&history, [day!(5), day!(6)].into_iter(), None);
+        assert_eq!(comparisons.len(), 1);
+        let comparison = &comparisons[0];
+        assert_eq!(comparison.baseline.commit.as_deref(), Some("aaaaaaa"));
+        assert_eq!(comparison.current.commit.as_deref(), Some("bbbbbbb"));
+        assert_eq!(comparison.stages.len(), 1);
+        assert_eq!(comparison.stages[0].stage, "Part 1");
+        assert!(matches!(comparison.stages[0].change, Change::Regression(_)));
+    }
+
+    #[test]
+    fn compares_against_chosen_baseline() {
+        let history = vec![
+            run("aaaaaaa", &[150, 151, 149, 150, 152, 148]),
+            run("bbbbbbb", &[100, 101, 99, 100, 102, 98]),
+            run("ccccccc", &[120, 121, 119, 120, 122, 118]),
+        ];
+
+        let comparisons = compare(&history, [day!(5)].into_iter(), Some("aaa"));
+        let comparison = &comparisons[0];
+        assert_eq!(comparison.baseline.commit.as_deref(), Some("aaaaaaa"));
+        assert!(matches!(
+            comparison.stages[0].change,
+            Change::Improvement(_)
+        ));
+
+        assert!(compare(&history, [day!(5)].into_iter(), Some("ddd")).is_empty());
+        assert!(compare(&history[..1], [day!(5)].into_iter(), None).is_empty());
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let run = run("aaaaaaa", &[1, 2, 3]);
+        let json = JsonValue::from(&run);
+        let parsed = Run::try_from(&json).unwrap();
+        assert_eq!(parsed.environment, run.environment);
+        assert_eq!(parsed.data, run.data);
+    }
+}
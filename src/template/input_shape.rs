@@ -0,0 +1,655 @@
+//! Infers the shape of a puzzle input, to scaffold a matching parser.
+//!
+//! An input is made up of sections separated by blank lines. The lines of each section are
+//! recognized, in this order, as:
+//!  1. a grid of characters: at least two lines of the same width, without whitespace or commas.
+//!  2. numbers: lines of numbers separated by commas or whitespace, or one number per line.
+//!  3. `key: value` lines, where the values can be numbers or text.
+//!  4. a `header:` line, followed by the lines of another shape.
+//!  5. plain lines of text.
+//!
+//! The parser stub for a shape is built from the helpers in [`crate::parse`] and `nom` parsers,
+//! see [`InputShape::parser_stub`].
+use std::collections::{BTreeMap, BTreeSet};
+
+/// How the numbers on a line are separated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Separator {
+    Comma,
+    Whitespace,
+}
+
+/// The format of a list of numbers on a single line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// `None` if the line holds a single number.
+    pub separator: Option<Separator>,
+    pub signed: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputShape {
+    Grid {
+        width: usize,
+        height: usize,
+    },
+    Numbers {
+        format: NumberFormat,
+        single_line: bool,
+    },
+    KeyValue {
+        /// `None` if the values are text.
+        numbers: Option<NumberFormat>,
+        single_line: bool,
+    },
+    Headed(Box<InputShape>),
+    Lines {
+        single_line: bool,
+    },
+    Sections(Vec<InputShape>),
+}
+
+/// Parser code for an input shape, to be inserted into a module template.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParserStub {
+    /// A description of the inferred shape.
+    pub shape: String,
+    /// The `use` declarations of the parse helpers and `nom` parsers.
+    pub imports: String,
+    /// A `parse` function and its helpers.
+    pub parser: String,
+    /// A test that parses the example input.
+    pub test: String,
+}
+
+/// Parse a line of numbers, returning its format.
+fn number_format(line: &str) -> Option<NumberFormat> {
+    if line.is_empty() || line != line.trim() {
+        return None;
+    }
+
+    let (separator, numbers): (_, Vec<&str>) = if line.contains(',') {
+        (
+            Some(Separator::Comma),
+            line.split(',').map(str::trim).collect(),
+        )
+    } else {
+        let numbers: Vec<&str> = line.split_whitespace().collect();
+        let separator = (numbers.len() > 1).then_some(Separator::Whitespace);
+        (separator, numbers)
+    };
+
+    if !numbers.iter().all(|x| x.parse::<i64>().is_ok()) {
+        return None;
+    }
+
+    Some(NumberFormat {
+        separator,
+        signed: numbers.iter().any(|x| x.starts_with('-')),
+    })
+}
+
+/// Combine the number formats of several lines, if they are compatible.
+fn merge_formats(formats: &[NumberFormat]) -> Option<NumberFormat> {
+    let separators: BTreeSet<_> = formats
+        .iter()
+        .filter_map(|f| f.separator)
+        .map(|s| s == Separator::Comma)
+        .collect();
+
+    let separator = match separators.iter().collect::<Vec<_>>()[..] {
+        [] => None,
+        [true] => Some(Separator::Comma),
+        [false] => Some(Separator::Whitespace),
+        _ => return None,
+    };
+
+    Some(NumberFormat {
+        separator,
+        signed: formats.iter().any(|f| f.signed),
+    })
+}
+
+fn split_key_value(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.split_once(": ")?;
+    let value = value.trim_start();
+    (!key.is_empty() && !value.is_empty()).then_some((key, value))
+}
+
+fn infer_section(lines: &[&str]) -> InputShape {
+    let single_line = lines.len() == 1;
+
+    // digits-only lines are grids, e.g. height maps, but comma-separated numbers are not.
+    let width = lines[0].chars().count();
+    let is_grid = lines.len() > 1
+        && width > 1
+        && lines.iter().all(|line| {
+            line.chars().count() == width && !line.contains(|c: char| c.is_whitespace() || c == ',')
+        });
+
+    if is_grid {
+        return InputShape::Grid {
+            width,
+            height: lines.len(),
+        };
+    }
+
+    if let Some(formats) = lines
+        .iter()
+        .map(|l| number_format(l))
+        .collect::<Option<Vec<_>>>()
+    {
+        if let Some(format) = merge_formats(&formats) {
+            return InputShape::Numbers {
+                format,
+                single_line,
+            };
+        }
+    }
+
+    if let Some(values) = lines
+        .iter()
+        .map(|line| split_key_value(line).map(|(_, value)| value))
+        .collect::<Option<Vec<_>>>()
+    {
+        let numbers = values
+            .iter()
+            .map(|value| number_format(value))
+            .collect::<Option<Vec<_>>>()
+            .and_then(|formats| merge_formats(&formats));
+
+        return InputShape::KeyValue {
+            numbers,
+            single_line,
+        };
+    }
+
+    if lines.len() > 1 && lines[0].ends_with(':') && !lines[0][..lines[0].len() - 1].contains(':') {
+        let mut inner = infer_section(&lines[1..]);
+        // a header introduces a list, even if it only has one line.
+        if let InputShape::Numbers { single_line, .. }
+        | InputShape::KeyValue { single_line, .. }
+        | InputShape::Lines { single_line } = &mut inner
+        {
+            *single_line = false;
+        }
+        return InputShape::Headed(Box::new(inner));
+    }
+
+    InputShape::Lines { single_line }
+}
+
+/// Infer the shape of an input. Returns `None` for an empty input.
+pub fn infer(input: &str) -> Option<InputShape> {
+    let mut sections: Vec<Vec<&str>> = vec![vec![]];
+
+    for line in input.trim().lines() {
+        if line.trim().is_empty() {
+            if !sections.last().unwrap().is_empty() {
+                sections.push(vec![]);
+            }
+        } else {
+            sections.last_mut().unwrap().push(line);
+        }
+    }
+
+    let mut shapes: Vec<InputShape> = sections
+        .iter()
+        .filter(|lines| !lines.is_empty())
+        .map(|lines| infer_section(lines))
+        .collect();
+
+    match shapes.len() {
+        0 => None,
+        1 => shapes.pop(),
+        _ => Some(InputShape::Sections(shapes)),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A `nom` parser expression and the type it produces.
+struct Parser {
+    ty: String,
+    expr: String,
+}
+
+/// Generates parser code, keeping track of the parse helpers and `nom` items it uses.
+#[derive(Default)]
+struct Generator {
+    helpers: BTreeSet<&'static str>,
+    imports: BTreeMap<&'static str, BTreeSet<&'static str>>,
+    functions: Vec<String>,
+}
+
+impl Generator {
+    fn import(&mut self, module: &'static str, items: &[&'static str]) {
+        self.imports.entry(module).or_default().extend(items);
+    }
+
+    /// Import a parser from [`crate::parse`], returning its name.
+    fn helper(&mut self, name: &'static str) -> &'static str {
+        self.helpers.insert(name);
+        name
+    }
+
+    fn lines_of(&mut self, item: Parser) -> Parser {
+        Parser {
+            ty: format!("Vec<{}>", item.ty),
+            expr: format!("{}({})", self.helper("lines"), item.expr),
+        }
+    }
+
+    fn sections_of(&mut self, section: Parser) -> Parser {
+        Parser {
+            ty: format!("Vec<{}>", section.ty),
+            expr: format!("{}({})", self.helper("sections"), section.expr),
+        }
+    }
+
+    fn numbers(&mut self, format: NumberFormat) -> Parser {
+        let (ty, number) = match format.signed {
+            true => ("i64", self.helper("signed")),
+            false => ("u64", self.helper("unsigned")),
+        };
+
+        match format.separator {
+            None => Parser {
+                ty: ty.into(),
+                expr: number.into(),
+            },
+            // `list` accepts both commas and whitespace.
+            Some(_) => Parser {
+                ty: format!("Vec<{ty}>"),
+                expr: format!("{}({number})", self.helper("list")),
+            },
+        }
+    }
+
+    fn text(&mut self) -> Parser {
+        self.import("bytes::complete", &["is_not"]);
+        Parser {
+            ty: "&str".into(),
+            expr: r#"is_not("\r\n")"#.into(),
+        }
+    }
+
+    fn section(&mut self, shape: &InputShape) -> Parser {
+        match shape {
+            InputShape::Grid { .. } => {
+                self.import("character::complete", &["none_of"]);
+                self.import("multi", &["many1"]);
+                self.lines_of(Parser {
+                    ty: "Vec<char>".into(),
+                    expr: r#"many1(none_of("\r\n"))"#.into(),
+                })
+            }
+            InputShape::Numbers {
+                format,
+                single_line,
+            } => {
+                let line = self.numbers(*format);
+                match single_line {
+                    true => line,
+                    false => self.lines_of(line),
+                }
+            }
+            InputShape::KeyValue {
+                numbers,
+                single_line,
+            } => {
+                let value = match numbers {
+                    Some(format) => self.numbers(*format),
+                    None => self.text(),
+                };
+                self.import("bytes::complete", &["is_not"]);
+                self.import("character::complete", &["char", "space1"]);
+                self.import("sequence", &["pair", "separated_pair"]);
+                let entry = Parser {
+                    ty: format!("(&str, {})", value.ty),
+                    expr: format!(
+                        r#"separated_pair(is_not(":\r\n"), pair(char(':'), space1), {})"#,
+                        value.expr
+                    ),
+                };
+                match single_line {
+                    true => entry,
+                    false => self.lines_of(entry),
+                }
+            }
+            InputShape::Headed(inner) => {
+                let inner = self.section(inner);
+                self.import("bytes::complete", &["is_not"]);
+                self.import("character::complete", &["char", "line_ending"]);
+                self.import("sequence", &["pair", "terminated"]);
+                Parser {
+                    ty: format!("(&str, {})", inner.ty),
+                    expr: format!(
+                        r#"pair(terminated(is_not(":\r\n"), pair(char(':'), line_ending)), {})"#,
+                        inner.expr
+                    ),
+                }
+            }
+            InputShape::Lines { single_line } => {
+                let line = self.text();
+                match single_line {
+                    true => line,
+                    false => self.lines_of(line),
+                }
+            }
+            InputShape::Sections(sections) => self.sections(sections),
+        }
+    }
+
+    /// Add a named parser function, returning a parser that calls it.
+    fn function(&mut self, name: &str, parser: Parser) -> Parser {
+        self.import("", &["IResult"]);
+        self.functions.push(format!(
+            "fn {name}(input: &str) -> IResult<&str, {}> {{\n    {}(input)\n}}\n",
+            parser.ty, parser.expr
+        ));
+        Parser {
+            ty: parser.ty,
+            expr: name.into(),
+        }
+    }
+
+    fn sections(&mut self, sections: &[InputShape]) -> Parser {
+        let (first, rest) = sections.split_first().unwrap();
+        let is_uniform =
+            |shapes: &[InputShape]| shapes.windows(2).all(|w| same_parser(&w[0], &w[1]));
+
+        if is_uniform(sections) {
+            let section = self.section(first);
+            let section = self.function("parse_section", section);
+            return self.sections_of(section);
+        }
+
+        if rest.len() == 1 || is_uniform(rest) {
+            let head = self.section(first);
+            let head = self.function("parse_head", head);
+            let section = self.section(&rest[0]);
+            let (name, many) = match rest.len() {
+                1 => ("parse_body", false),
+                _ => ("parse_section", true),
+            };
+            let mut body = self.function(name, section);
+            if many {
+                body = self.sections_of(body);
+            }
+            self.import("sequence", &["separated_pair"]);
+            let blank_line = self.helper("blank_line");
+            return Parser {
+                ty: format!("({}, {})", head.ty, body.ty),
+                expr: format!("separated_pair({}, {blank_line}, {})", head.expr, body.expr),
+            };
+        }
+
+        // sections of different shapes are left to the solution as lines of text.
+        let section = self.section(&InputShape::Lines { single_line: false });
+        let section = self.function("parse_section", section);
+        self.sections_of(section)
+    }
+
+    /// The `use` declarations for the imported parse helpers and `nom` items, in `rustfmt` order.
+    fn use_declarations(&self) -> String {
+        let helpers: Vec<&str> = self.helpers.iter().copied().collect();
+        let helpers = match helpers[..] {
+            [] => String::new(),
+            [helper] => format!("use advent_of_code::parse::{helper};\n"),
+            _ => format!("use advent_of_code::parse::{{{}}};\n", helpers.join(", ")),
+        };
+
+        let mut paths: Vec<String> = self
+            .imports
+            .iter()
+            .filter(|(module, _)| !module.is_empty())
+            .map(|(module, items)| {
+                let items: Vec<&str> = items.iter().copied().collect();
+                match items[..] {
+                    [item] => format!("{module}::{item}"),
+                    _ => format!("{module}::{{{}}}", sorted_items(items).join(", ")),
+                }
+            })
+            .collect();
+
+        if let Some(items) = self.imports.get("") {
+            paths.extend(items.iter().map(|item| item.to_string()));
+        }
+
+        let nom = match paths[..] {
+            [] => String::new(),
+            [ref path] => format!("use nom::{path};\n"),
+            _ => format!("use nom::{{\n    {},\n}};\n", paths.join(",\n    ")),
+        };
+
+        helpers + &nom
+    }
+}
+
+/// Sort items like `rustfmt`, with `self` first.
+fn sorted_items(mut items: Vec<&str>) -> Vec<&str> {
+    items.sort_by_key(|item| (*item != "self", *item));
+    items
+}
+
+/// Whether two shapes are parsed by the same parser, e.g. grids of different sizes.
+fn same_parser(a: &InputShape, b: &InputShape) -> bool {
+    let mut generator = Generator::default();
+    generator.section(a).expr == generator.section(b).expr
+}
+
+impl InputShape {
+    /// A short description of the shape, e.g. `a grid of 140x140 characters`.
+    pub fn describe(&self) -> String {
+        let numbers = |format: &NumberFormat| match format.separator {
+            None => "a number",
+            Some(Separator::Comma) => "comma-separated numbers",
+            Some(Separator::Whitespace) => "space-separated numbers",
+        };
+
+        match self {
+            InputShape::Grid { width, height } => {
+                format!("a grid of {width}x{height} characters")
+            }
+            InputShape::Numbers {
+                format,
+                single_line: true,
+            } => format!("a line of {}", numbers(format)),
+            InputShape::Numbers { format, .. } => format!("lines of {}", numbers(format)),
+            InputShape::KeyValue { numbers: n, .. } => match n {
+                Some(format) => format!("`key: value` lines with {} as values", numbers(format)),
+                None => "`key: value` lines".into(),
+            },
+            InputShape::Headed(inner) => {
+                format!("a `header:` line followed by {}", inner.describe())
+            }
+            InputShape::Lines { single_line: true } => "a line of text".into(),
+            InputShape::Lines { .. } => "lines of text".into(),
+            InputShape::Sections(sections) => {
+                // consecutive sections of the same shape are described once.
+                let mut groups: Vec<(String, usize)> = vec![];
+                for description in sections.iter().map(InputShape::describe) {
+                    match groups.last_mut() {
+                        Some((last, count)) if *last == description => *count += 1,
+                        _ => groups.push((description, 1)),
+                    }
+                }
+                let groups: Vec<String> = groups
+                    .into_iter()
+                    .map(|(description, count)| match count {
+                        1 => description,
+                        _ => format!("{count} sections of {description}"),
+                    })
+                    .collect();
+                format!(
+                    "sections separated by blank lines: {}",
+                    groups.join(", then ")
+                )
+            }
+        }
+    }
+
+    /// Generate a `parse` function for this shape, built from the helpers in [`crate::parse`] and
+    /// `nom` parsers, and a test that parses the example input with it.
+    pub fn parser_stub(&self) -> ParserStub {
+        let mut generator = Generator::default();
+        let parser = generator.section(self);
+        let parse_all = generator.helper("parse_all");
+
+        let mut functions = generator.functions.clone();
+        functions.push(format!(
+            "/// Parses the input: {}.\nfn parse(input: &str) -> Option<{}> {{\n    {parse_all}(input, {}).ok()\n}}\n",
+            self.describe(),
+            parser.ty,
+            parser.expr
+        ));
+
+        ParserStub {
+            shape: self.describe(),
+            imports: generator.use_declarations(),
+            parser: functions.join("\n"),
+            test: TEST.into(),
+        }
+    }
+}
+
+const TEST: &str = r#"    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        assert!(parse(&input).is_some());
+    }
+"#;
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{infer, InputShape, NumberFormat, Separator};
+
+    #[test]
+    fn infers_grids() {
+        assert_eq!(
+            infer("467..114..\n...*......\n..35..633.\n"),
+            Some(InputShape::Grid {
+                width: 10,
+                height: 3
+            })
+        );
+    }
+
+    #[test]
+    fn infers_numbers() {
+        assert_eq!(
+            infer("0 3 6 9\n1 -3 6 10\n"),
+            Some(InputShape::Numbers {
+                format: NumberFormat {
+                    separator: Some(Separator::Whitespace),
+                    signed: true
+                },
+                single_line: false
+            })
+        );
+        assert_eq!(
+            infer("3,4,3,1,2\n"),
+            Some(InputShape::Numbers {
+                format: NumberFormat {
+                    separator: Some(Separator::Comma),
+                    signed: false
+                },
+                single_line: true
+            })
+        );
+        assert_eq!(
+            infer("1,2,3\n4,5,6\n"),
+            Some(InputShape::Numbers {
+                format: NumberFormat {
+                    separator: Some(Separator::Comma),
+                    signed: false
+                },
+                single_line: false
+            })
+        );
+        assert_eq!(
+            infer("199\n200\n1\n"),
+            Some(InputShape::Numbers {
+                format: NumberFormat {
+                    separator: None,
+                    signed: false
+                },
+                single_line: false
+            })
+        );
+    }
+
+    #[test]
+    fn infers_key_value_lines() {
+        assert_eq!(
+            infer("Time:      7  15   30\nDistance:  9  40  200\n"),
+            Some(InputShape::KeyValue {
+                numbers: Some(NumberFormat {
+                    separator: Some(Separator::Whitespace),
+                    signed: false
+                }),
+                single_line: false
+            })
+        );
+        assert_eq!(
+            infer("Game 1: 3 blue, 4 red\nGame 2: 1 blue\n"),
+            Some(InputShape::KeyValue {
+                numbers: None,
+                single_line: false
+            })
+        );
+    }
+
+    #[test]
+    fn infers_sections() {
+        let input = "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\nsoil-to-fertilizer map:\n0 15 37\n";
+        let Some(InputShape::Sections(sections)) = infer(input) else {
+            panic!("expected sections");
+        };
+        assert_eq!(sections.len(), 3);
+        assert!(matches!(sections[0], InputShape::KeyValue { .. }));
+        assert!(matches!(sections[1], InputShape::Headed(_)));
+        assert_eq!(
+            infer("LR\n\nAAA = (11B, XXX)\n11B = (XXX, ZZZ)\n"),
+            Some(InputShape::Sections(vec![
+                InputShape::Lines { single_line: true },
+                InputShape::Lines { single_line: false }
+            ]))
+        );
+    }
+
+    #[test]
+    fn generates_parser_stubs() {
+        let stub = infer("seeds: 79 14\n\nmap:\n50 98 2\n\nmap:\n0 15 37\n")
+            .unwrap()
+            .parser_stub();
+        assert!(stub.parser.contains("fn parse_head("));
+        assert!(stub.parser.contains("fn parse_section("));
+        assert!(stub.parser.contains(
+            "fn parse(input: &str) -> Option<((&str, Vec<u64>), Vec<(&str, Vec<Vec<u64>>)>)>"
+        ));
+        assert!(stub.imports.starts_with(
+            "use advent_of_code::parse::{blank_line, lines, list, parse_all, sections, unsigned};\nuse nom::{\n"
+        ));
+        assert!(stub
+            .parser
+            .contains("separated_pair(parse_head, blank_line, sections(parse_section))"));
+        assert_eq!(
+            stub.shape,
+            "sections separated by blank lines: `key: value` lines with space-separated numbers \
+            as values, then 2 sections of a `header:` line followed by lines of space-separated \
+            numbers"
+        );
+        assert!(stub
+            .imports
+            .contains("character::complete::{char, line_ending, space1},"));
+        assert_eq!(
+            infer("1,2\n3,4\n").unwrap().parser_stub().imports,
+            "use advent_of_code::parse::{lines, list, parse_all, unsigned};\n"
+        );
+        assert_eq!(infer("\n"), None);
+    }
+}
@@ -0,0 +1,226 @@
+//! Expected answers for a set of input files.
+//!
+//! A manifest lists input files by name, together with the expected answer of each part:
+//! `{ "data": [{ "input": "alice.txt", "part_1": "35", "part_2": null }] }`.
+//! Parts without an expected answer can be `null` or omitted.
+use std::{collections::HashMap, fs, io, path::Path, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::answers::Verdict;
+use crate::template::Answer;
+
+/// Expected answers for a single input file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ManifestEntry {
+    /// File name of the input, relative to the manifest.
+    pub input: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Expected answers for a set of input files.
+#[derive(Clone, Debug, Default)]
+pub struct Manifest {
+    pub data: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    /// Read a manifest from `path`. A missing file yields an empty manifest.
+    pub fn read_from_file(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(s) => Manifest::try_from(s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Manifest::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Write the manifest to `path` as JSON.
+    pub fn store_file(&self, path: &Path) -> Result<(), io::Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Returns `true` if any input has an expected answer.
+    pub fn has_answers(&self) -> bool {
+        self.data
+            .iter()
+            .any(|e| e.part_1.is_some() || e.part_2.is_some())
+    }
+
+    /// Returns the expected answer of a part for an input, if any.
+    pub fn get(&self, input: &str, part: u8) -> Option<&str> {
+        let entry = self.data.iter().find(|e| e.input == input)?;
+        match part {
+            1 => entry.part_1.as_deref(),
+            2 => entry.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Compare the answer a part produced for an input with its expected answer.
+    pub fn verify(&self, input: &str, part: u8, actual: Option<&str>) -> Verdict {
+        match (self.get(input, part), actual) {
+            (Some(expected), Some(actual)) if Answer::parse(actual).matches(expected) => {
+                Verdict::Correct
+            }
+            (Some(expected), actual) => Verdict::Wrong {
+                expected: expected.to_string(),
+                actual: actual.unwrap_or("no answer").to_string(),
+            },
+            (None, _) => Verdict::Unknown,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Manifest> for JsonValue {
+    fn from(value: &Manifest) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Manifest {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Manifest {
+            data: json_data
+                .iter()
+                .map(ManifestEntry::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&ManifestEntry> for JsonValue {
+    fn from(value: &ManifestEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("input".into(), JsonValue::String(value.input.clone()));
+
+        map.insert(
+            "part_1".into(),
+            match value.part_1.clone() {
+                Some(x) => JsonValue::String(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "part_2".into(),
+            match value.part_2.clone() {
+                Some(x) => JsonValue::String(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for ManifestEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected manifest entry to be a JSON object.")?;
+
+        let input = json
+            .get("input")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected manifest.input to be a string.")?;
+
+        let part = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or(format!("Expected manifest.{key} to be null or string.")),
+        };
+
+        Ok(ManifestEntry {
+            input: input.clone(),
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Manifest;
+    use crate::template::answers::Verdict;
+    use tinyjson::JsonValue;
+
+    fn get_mock_manifest() -> Manifest {
+        let json = r#"{ "data": [
+            { "input": "alice.txt", "part_1": "35", "part_2": null },
+            { "input": "bob.txt", "part_2": "46" }
+        ] }"#;
+        Manifest::try_from(json.to_string()).unwrap()
+    }
+
+    #[test]
+    fn reads_optional_parts() {
+        let manifest = get_mock_manifest();
+        assert_eq!(manifest.get("alice.txt", 1), Some("35"));
+        assert_eq!(manifest.get("alice.txt", 2), None);
+        assert_eq!(manifest.get("bob.txt", 1), None);
+        assert_eq!(manifest.get("bob.txt", 2), Some("46"));
+        assert_eq!(manifest.get("carol.txt", 1), None);
+    }
+
+    #[test]
+    fn verifies_answers() {
+        let manifest = get_mock_manifest();
+        assert_eq!(
+            manifest.verify("alice.txt", 1, Some("35")),
+            Verdict::Correct
+        );
+        assert_eq!(
+            manifest.verify("bob.txt", 2, None),
+            Verdict::Wrong {
+                expected: "46".into(),
+                actual: "no answer".into()
+            }
+        );
+        assert_eq!(manifest.verify("alice.txt", 2, Some("1")), Verdict::Unknown);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let manifest = get_mock_manifest();
+        let json = JsonValue::from(&manifest).stringify().unwrap();
+        assert_eq!(Manifest::try_from(json).unwrap().data, manifest.data);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_manifests() {
+        Manifest::try_from(r#"{ "data": [{ "part_1": "35" }] }"#.to_string()).unwrap();
+    }
+}
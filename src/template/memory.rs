@@ -0,0 +1,231 @@
+//! Heap usage of a solution, read from the profiles written by solutions built with `dhat-heap`.
+use std::{collections::HashMap, fmt::Display, fs, str::FromStr};
+use tinyjson::JsonValue;
+
+/// Heap usage of a single run of a solution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Bytes allocated at the point of maximum heap usage.
+    pub peak_bytes: u64,
+    /// Bytes allocated over the whole run.
+    pub total_bytes: u64,
+    /// Number of allocations over the whole run.
+    pub allocations: u64,
+}
+
+impl MemoryStats {
+    /// Read the heap profile written by dhat to `path`.
+    pub fn read_dhat_file(path: &str) -> Result<Self, String> {
+        let s = fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+        Self::from_dhat(&s)
+    }
+
+    /// Parse a heap profile in dhat's JSON format. The totals are the sums over all program
+    /// points, `tb` and `tbk` for all allocations and `gb` for the point of maximum heap usage.
+    pub fn from_dhat(s: &str) -> Result<Self, String> {
+        let json = JsonValue::from_str(s).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected heap profile to be an object.")?;
+
+        // ad-hoc profiles don't track the heap and lack the peak.
+        match json.get("mode").and_then(|v| v.get::<String>()) {
+            Some(mode) if mode == "rust-heap" => {}
+            _ => return Err("expected a heap profile of a Rust program.".into()),
+        }
+
+        let program_points = json
+            .get("pps")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected heap profile to have an array `pps`.")?;
+
+        let mut stats = MemoryStats {
+            peak_bytes: 0,
+            total_bytes: 0,
+            allocations: 0,
+        };
+
+        for point in program_points {
+            let point = point
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("expected program point to be an object.")?;
+
+            let number = |key: &str| {
+                point
+                    .get(key)
+                    .and_then(|v| v.get::<f64>().copied())
+                    .ok_or(format!("expected program point.{key} to be a number."))
+            };
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            {
+                stats.total_bytes += number("tb")? as u64;
+                stats.allocations += number("tbk")? as u64;
+                stats.peak_bytes += number("gb")? as u64;
+            }
+        }
+
+        Ok(stats)
+    }
+}
+
+/// Format a number of bytes for display, e.g. `1.2 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "peak {} · {} in {} allocations",
+            format_bytes(self.peak_bytes),
+            format_bytes(self.total_bytes),
+            self.allocations
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&MemoryStats> for JsonValue {
+    fn from(value: &MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert(
+                "peak_bytes".into(),
+                JsonValue::Number(value.peak_bytes as f64),
+            );
+            map.insert(
+                "total_bytes".into(),
+                JsonValue::Number(value.total_bytes as f64),
+            );
+            map.insert(
+                "allocations".into(),
+                JsonValue::Number(value.allocations as f64),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(MemoryStats {
+            peak_bytes: number("peak_bytes")? as u64,
+            total_bytes: number("total_bytes")? as u64,
+            allocations: number("allocations")? as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, MemoryStats};
+    use tinyjson::JsonValue;
+
+    const PROFILE: &str = r#"{
+        "dhatFileVersion": 2,
+        "mode": "rust-heap",
+        "verb": "Allocated",
+        "bklt": true,
+        "bkacc": false,
+        "tu": "µs",
+        "Mtu": "s",
+        "tuth": 10,
+        "cmd": "target/dhat/2023-01",
+        "pid": 4242,
+        "tg": 120,
+        "te": 300,
+        "pps": [
+            { "tb": 200, "tbk": 2, "tl": 50, "mb": 200, "mbk": 2, "gb": 200, "gbk": 2, "eb": 0, "ebk": 0, "fs": [1, 2] },
+            { "tb": 76, "tbk": 1, "tl": 10, "mb": 76, "mbk": 1, "gb": 32, "gbk": 1, "eb": 0, "ebk": 0, "fs": [3] }
+        ],
+        "ftbl": ["[root]", "a", "b", "c"]
+    }"#;
+
+    #[test]
+    fn parses_heap_profiles() {
+        let stats = MemoryStats::from_dhat(PROFILE).unwrap();
+        assert_eq!(
+            stats,
+            MemoryStats {
+                peak_bytes: 232,
+                total_bytes: 276,
+                allocations: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn handles_profiles_without_allocations() {
+        let profile = r#"{ "mode": "rust-heap", "pps": [] }"#;
+        let stats = MemoryStats::from_dhat(profile).unwrap();
+        assert_eq!(stats.peak_bytes, 0);
+        assert_eq!(stats.allocations, 0);
+    }
+
+    #[test]
+    fn rejects_ad_hoc_profiles() {
+        let profile = r#"{ "mode": "rust-ad-hoc", "pps": [{ "tb": 1, "tbk": 1, "fs": [] }] }"#;
+        assert!(MemoryStats::from_dhat(profile).is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_profiles() {
+        assert!(MemoryStats::from_dhat("dhat").is_err());
+        assert!(MemoryStats::from_dhat(r#"{ "mode": "rust-heap" }"#).is_err());
+        assert!(
+            MemoryStats::from_dhat(r#"{ "mode": "rust-heap", "pps": [{ "tb": 1 }] }"#).is_err()
+        );
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = MemoryStats::from_dhat(PROFILE).unwrap();
+        let json = JsonValue::from(&stats);
+        assert_eq!(MemoryStats::try_from(&json).unwrap(), stats);
+    }
+}
@@ -0,0 +1,130 @@
+//! One-time migration of the data folder from the single-year layout to `data/<year>`.
+//!
+//! Before solutions were namespaced by year, inputs, examples and puzzles lived directly in
+//! `data/inputs`, `data/examples` and `data/puzzles`, next to `data/timings.json`,
+//! `data/answers.json` and `data/submissions.json`. `cargo migrate --year <year>` moves these
+//! files into the data folder of that year.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::Year;
+
+const LEGACY_DIRS: [&str; 3] = ["inputs", "examples", "puzzles"];
+const LEGACY_FILES: [&str; 3] = ["timings.json", "answers.json", "submissions.json"];
+
+/// Move data of the single-year layout into `data/<year>` and report every moved file.
+/// Files that already exist in `data/<year>` are left in place, so nothing is overwritten.
+pub fn handle(year: Year) {
+    match migrate(Path::new("data"), year) {
+        Ok(moved) if moved.is_empty() => println!("No data to move to {}.", year.data_dir()),
+        Ok(moved) => {
+            for path in &moved {
+                println!("Moved {} to {}.", path.display(), year.data_dir());
+            }
+            if has_legacy_data() {
+                println!(
+                    "Files that already exist in {} were left in place.",
+                    year.data_dir()
+                );
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to move legacy data to {}: {e}", year.data_dir());
+            process::exit(1);
+        }
+    }
+}
+
+/// Returns `true` if the data folder still has data of the single-year layout.
+pub fn has_legacy_data() -> bool {
+    let root = Path::new("data");
+    LEGACY_DIRS.iter().any(|name| root.join(name).is_dir())
+        || LEGACY_FILES.iter().any(|name| root.join(name).is_file())
+}
+
+/// Move legacy data below `root` into `root/<year>`, returning the moved paths.
+fn migrate(root: &Path, year: Year) -> Result<Vec<PathBuf>, io::Error> {
+    let year_dir = root.join(year.to_string());
+    let mut moved = vec![];
+
+    for name in LEGACY_DIRS {
+        let dir = root.join(name);
+        if !dir.is_dir() {
+            continue;
+        }
+
+        let target_dir = year_dir.join(name);
+        fs::create_dir_all(&target_dir)?;
+
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let target = target_dir.join(path.file_name().unwrap_or_default());
+            if path.file_name().is_some_and(|n| n == ".keep") || target.exists() {
+                continue;
+            }
+            fs::rename(&path, &target)?;
+            moved.push(path);
+        }
+
+        let keep = dir.join(".keep");
+        if fs::read_dir(&dir)?.all(|e| e.is_ok_and(|e| e.path() == keep)) {
+            fs::remove_dir_all(&dir)?;
+        }
+    }
+
+    for name in LEGACY_FILES {
+        let path = root.join(name);
+        let target = year_dir.join(name);
+        if path.is_file() && !target.exists() {
+            fs::create_dir_all(&year_dir)?;
+            fs::rename(&path, &target)?;
+            moved.push(path);
+        }
+    }
+
+    Ok(moved)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::migrate;
+    use crate::year;
+    use std::{env, fs};
+
+    #[test]
+    fn moves_legacy_data() {
+        let root = env::temp_dir().join(format!("aoc-migrate-{}", std::process::id()));
+        let year_dir = root.join("2023");
+        fs::create_dir_all(root.join("inputs")).unwrap();
+        fs::create_dir_all(root.join("examples")).unwrap();
+        fs::create_dir_all(year_dir.join("examples")).unwrap();
+        fs::write(root.join("inputs/01.txt"), "input").unwrap();
+        fs::write(root.join("inputs/.keep"), "").unwrap();
+        fs::write(root.join("examples/01.txt"), "old").unwrap();
+        fs::write(year_dir.join("examples/01.txt"), "new").unwrap();
+        fs::write(root.join("timings.json"), "{}").unwrap();
+
+        let moved = migrate(&root, year!(2023)).unwrap();
+        assert_eq!(moved.len(), 2);
+        assert_eq!(
+            fs::read_to_string(year_dir.join("inputs/01.txt")).unwrap(),
+            "input"
+        );
+        assert!(year_dir.join("timings.json").exists());
+        assert!(!root.join("inputs").exists());
+        // existing files of the year are never overwritten.
+        assert_eq!(
+            fs::read_to_string(year_dir.join("examples/01.txt")).unwrap(),
+            "new"
+        );
+        assert!(root.join("examples/01.txt").exists());
+
+        assert!(migrate(&root, year!(2023)).unwrap().is_empty());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,15 +1,40 @@
-use std::{env, fs};
+use std::{env, fs, path::Path};
 
+use crate::template::manifest::Manifest;
+
+pub mod alloc;
 pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod migrate;
+pub mod registry;
 pub mod runner;
 
+pub use answer::Answer;
 pub use day::*;
+pub use puzzle::*;
+pub use year::*;
 
+mod against;
+mod answer;
+mod answers;
 mod day;
+mod examples;
+mod history;
+mod input_shape;
+mod manifest;
+mod memory;
+mod puzzle;
 mod readme_benchmarks;
+mod report;
+mod run_inputs;
 mod run_multi;
+mod stats;
+mod submissions;
+mod templates;
 mod timings;
+mod watch;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -17,28 +42,81 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.data_path(folder, "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(puzzle.year.data_dir())
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Helper function for tests that runs `func` on every example listed in the example manifest of
+/// a puzzle that has an expected answer for `part`, and asserts that it returns that answer.
+///
+/// # Panics
+/// If an example does not produce its expected answer, if no example has an expected answer for
+/// `part`, or if the manifest or an example can't be read.
+pub fn check_examples<T: Into<Answer>>(
+    puzzle: PuzzleId,
+    part: u8,
+    func: impl Fn(&str) -> Option<T>,
+) {
+    let manifest_path = puzzle.example_manifest_path();
+    let manifest = Manifest::read_from_file(Path::new(&manifest_path))
+        .expect("could not read example manifest");
+    let dir = env::current_dir()
+        .unwrap()
+        .join(puzzle.year.data_dir())
+        .join("examples");
+    let mut checked = 0;
+
+    for entry in &manifest.data {
+        let Some(expected) = manifest.get(&entry.input, part) else {
+            continue;
+        };
+
+        let input =
+            fs::read_to_string(dir.join(&entry.input)).expect("could not open example file");
+        let actual = func(&input).map(Into::<Answer>::into);
+
+        assert!(
+            actual
+                .as_ref()
+                .is_some_and(|actual| actual.matches(expected)),
+            "wrong answer for part {part} of example `{}`\n expected: {expected}\n   actual: {}",
+            entry.input,
+            actual.map_or("no answer".into(), |actual| actual.to_string())
+        );
+        checked += 1;
+    }
+
+    assert!(
+        checked > 0,
+        "no example has an expected answer for part {part}, add one to `{manifest_path}`"
+    );
+}
+
+/// Creates the constants `YEAR`, `DAY` and `PUZZLE` and sets up the input and runner for each part.
+/// Also implements [`registry::Solution`] for a `Solution` struct, to run the solution in-process.
+///
+/// The year is taken from the file name of the solution, e.g. `src/bin/2023-05.rs`.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// The optional `parse = <fn>` parameter declares a parse stage shared by both parts. The parse
+/// function receives the raw input (`fn(&str) -> Option<P>`), is timed separately, and both parts
+/// receive a reference to its output (`fn(&P) -> Option<T>`).
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,19 +128,92 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_one, 1]);
+    };
+    ($day:expr, 2, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_two, 2]);
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            let input = read_input(PUZZLE);
+            #[cfg(feature = "dhat-heap")]
+            let _profiler = start_heap_profiler(PUZZLE);
+            $( run_part($func, &input, PUZZLE, $part); )*
+        }
+
+        impl $crate::template::registry::Solution for Solution {
+            fn puzzle(&self) -> $crate::template::PuzzleId {
+                PUZZLE
+            }
+
+            fn run(
+                &self,
+                input: &str,
+                options: &$crate::template::runner::RunOptions,
+            ) -> Vec<$crate::template::registry::PartReport> {
+                use $crate::template::runner::*;
+                let mut reports = vec![];
+                $( reports.extend(solve_part($func, input, PUZZLE, $part, options)); )*
+                reports
+            }
+        }
+    };
+    (@impl $day:expr, parse = $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            let input = read_input(PUZZLE);
+            #[cfg(feature = "dhat-heap")]
+            let _profiler = start_heap_profiler(PUZZLE);
+            if let Some(parsed) = run_parse($parse, &input, PUZZLE) {
+                // the closure allows parts to accept e.g. `&[T]` for a parsed `Vec<T>`.
+                $( run_part(|parsed| $func(parsed), &parsed, PUZZLE, $part); )*
+            }
+        }
+
+        impl $crate::template::registry::Solution for Solution {
+            fn puzzle(&self) -> $crate::template::PuzzleId {
+                PUZZLE
+            }
+
+            fn run(
+                &self,
+                input: &str,
+                options: &$crate::template::runner::RunOptions,
+            ) -> Vec<$crate::template::registry::PartReport> {
+                use $crate::template::runner::*;
+                let (parsed, report) = solve_parse($parse, input, PUZZLE, options);
+                let mut reports = vec![report];
+                if let Some(parsed) = parsed {
+                    $( reports.extend(solve_part(|parsed| $func(parsed), &parsed, PUZZLE, $part, options)); )*
+                }
+                reports
+            }
+        }
+    };
+
+    (@setup $day:expr) => {
+        /// The current year.
+        const YEAR: $crate::template::Year = $crate::template::Year::__from_file_path(file!());
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
+        /// The current puzzle.
+        const PUZZLE: $crate::template::PuzzleId = $crate::template::PuzzleId::new(YEAR, DAY);
+
+        /// This solution, for the in-process registry of the main binary.
+        pub struct Solution;
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
-
-        fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
-        }
     };
 }
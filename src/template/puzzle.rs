@@ -0,0 +1,78 @@
+//! Identifies a puzzle by year and day, and locates its solution and data files.
+use std::fmt::Display;
+
+use crate::template::{Day, Year};
+
+/// Identifies the puzzle of a single day in a given year.
+///
+/// Solutions and their data are namespaced by year:
+/// the solution for day 5 of 2023 lives in `src/bin/2023-05.rs`,
+/// its input in `data/2023/inputs/05.txt`.
+///
+/// # Display
+/// This value displays as `<year>-<day>`, which is also the name of the solution binary.
+///
+/// ```
+/// # use advent_of_code::{Day, PuzzleId, Year};
+/// let puzzle = PuzzleId::new(Year::new(2023).unwrap(), Day::new(5).unwrap());
+/// assert_eq!(puzzle.to_string(), "2023-05");
+/// assert_eq!(puzzle.input_path(), "data/2023/inputs/05.txt");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Path of the solution binary, e.g. `src/bin/2023-05.rs`.
+    pub fn bin_path(self) -> String {
+        format!("src/bin/{self}.rs")
+    }
+
+    /// Path of a day's file in a data folder, e.g. `data/2023/examples/05.txt`.
+    pub fn data_path(self, folder: &str, extension: &str) -> String {
+        format!("{}/{folder}/{}.{extension}", self.year.data_dir(), self.day)
+    }
+
+    /// Path of the puzzle input, e.g. `data/2023/inputs/05.txt`.
+    pub fn input_path(self) -> String {
+        self.data_path("inputs", "txt")
+    }
+
+    /// Directory of additional inputs, e.g. `data/2023/inputs/05/`.
+    pub fn inputs_dir(self) -> String {
+        format!("{}/inputs/{}", self.year.data_dir(), self.day)
+    }
+
+    /// Path of the example input, e.g. `data/2023/examples/05.txt`.
+    pub fn example_path(self) -> String {
+        self.data_path("examples", "txt")
+    }
+
+    /// Path of the example manifest, e.g. `data/2023/examples/05.json`.
+    pub fn example_manifest_path(self) -> String {
+        self.data_path("examples", "json")
+    }
+
+    /// Path of the puzzle description, e.g. `data/2023/puzzles/05.md`.
+    pub fn puzzle_path(self) -> String {
+        self.data_path("puzzles", "md")
+    }
+
+    /// Path of the heap profile written by solutions built with `dhat-heap`,
+    /// e.g. `target/dhat-heap/2023-05.json`.
+    pub fn dhat_path(self) -> String {
+        format!("target/dhat-heap/{self}.json")
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
@@ -1,11 +1,15 @@
-/// Module that updates the readme me with timing information.
-/// The approach taken is similar to how `aoc-readme-stars` handles this.
+//! Module that updates the readme me with timing information.
+//! The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::memory::{format_bytes, MemoryStats};
+use crate::template::timings::{StageTiming, Timings};
+use crate::template::{PuzzleId, Year};
 
-static MARKER: &str = "<!--- benchmarking table --->";
+/// Each year has its own table, delimited by a pair of markers.
+fn get_marker(year: Year) -> String {
+    format!("<!--- benchmarking table {year} --->")
+}
 
 #[derive(Debug)]
 pub enum Error {
@@ -19,18 +23,23 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse README: {e}"),
+            Error::IO(e) => write!(f, "could not access README: {e}"),
+        }
+    }
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, year: Year) -> Result<TablePosition, Error> {
+    let marker = get_marker(year);
+    let matches: Vec<_> = readme.match_indices(&marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -51,78 +60,140 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(prefix: &str, year: Year, timings: Timings, total_millis: f64) -> String {
+    let header = format!("{prefix} {year} Benchmarks");
+    let marker = get_marker(year);
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    // only show the parse column if at least one solution declares a parse stage,
+    // and the memory columns if at least one solution was profiled.
+    let has_parse = timings.data.iter().any(|t| t.parse.is_some());
+    let has_memory = timings.data.iter().any(|t| t.memory.is_some());
+
+    let mut lines: Vec<String> = vec![marker.clone(), header, String::new()];
+
+    let mut columns = vec!["Day"];
+    if has_parse {
+        columns.push("Parse");
+    }
+    columns.extend(["Part 1", "Part 2"]);
+    if has_memory {
+        columns.extend(["Peak Heap", "Allocations"]);
+    }
+
+    lines.push(format!("| {} |", columns.join(" | ")));
+    lines.push(format!("| {}  |", vec![":---:"; columns.len()].join(" | ")));
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let path = format!("./{}", PuzzleId::new(year, timing.day).bin_path());
+        let format = |stage: &Option<StageTiming>| {
+            stage
+                .as_ref()
+                .map_or_else(|| "-".into(), StageTiming::formatted)
+        };
+        let parse = if has_parse {
+            format!(" `{}` |", format(&timing.parse))
+        } else {
+            String::new()
+        };
+        let memory = if has_memory {
+            format_memory(timing.memory.as_ref())
+        } else {
+            String::new()
+        };
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) |{} `{}` | `{}` |{}",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            parse,
+            format(&timing.part_1),
+            format(&timing.part_2),
+            memory
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+/// The memory columns of a day, e.g. `` `232 B` | `3 (276 B)` | ``.
+fn format_memory(memory: Option<&MemoryStats>) -> String {
+    match memory {
+        Some(memory) => format!(
+            " `{}` | `{} ({})` |",
+            format_bytes(memory.peak_bytes),
+            memory.allocations,
+            format_bytes(memory.total_bytes)
+        ),
+        None => " `-` | `-` |".into(),
+    }
+}
+
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let positions = locate_table(s, year)?;
+    let table = construct_table("##", year, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+pub fn update(year: Year, timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, timings, total_millis)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::update_content;
+    use crate::template::Year;
+    use crate::{
+        day,
+        template::memory::MemoryStats,
+        template::timings::{StageTiming, Timing, Timings},
+        year,
+    };
+
+    const MARKER: &str = "<!--- benchmarking table 2023 --->";
+    const YEAR: Year = year!(2023);
+
+    fn stage(millis: u64) -> Option<StageTiming> {
+        Some(StageTiming {
+            nanos: (millis * 1_000_000) as f64,
+            samples: 1,
+            stats: None,
+        })
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    part_1: stage(10),
+                    part_2: stage(20),
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    part_1: stage(30),
+                    part_2: stage(40),
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    part_1: stage(40),
+                    part_2: stage(50),
+                    ..Timing::new(day!(4))
                 },
             ],
+            history: vec![],
         }
     }
 
@@ -130,53 +201,123 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, YEAR, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, YEAR, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        update_content(&mut s, YEAR, get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.contains("## 2023 Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, YEAR, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, YEAR, get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        assert_eq!(
+            s.matches("## 2023 Benchmarks").collect::<Vec<&str>>().len(),
+            1
+        );
     }
 
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, YEAR, get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
-            "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "<!--- benchmarking table 2023 --->",
+            "## 2023 Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2023-01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2023-02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2023-04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
-            "<!--- benchmarking table --->",
+            "<!--- benchmarking table 2023 --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_parse_stage() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = stage(5);
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, YEAR, timings, 190.0).unwrap();
+        let expected = [
+            "foo",
+            "bar",
+            "<!--- benchmarking table 2023 --->",
+            "## 2023 Benchmarks",
+            "",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2023-01.rs) | `-` | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2023-02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2023-04.rs) | `-` | `40.0ms` | `50.0ms` |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table 2023 --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_memory() {
+        let timings = get_mock_timings().with_memory(
+            day!(2),
+            MemoryStats {
+                peak_bytes: 232,
+                total_bytes: 2048,
+                allocations: 3,
+            },
+        );
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, YEAR, timings, 190.0).unwrap();
+        let expected = [
+            "foo",
+            "bar",
+            "<!--- benchmarking table 2023 --->",
+            "## 2023 Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 | Peak Heap | Allocations |",
+            "| :---: | :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2023-01.rs) | `10.0ms` | `20.0ms` | `-` | `-` |",
+            "| [Day 2](./src/bin/2023-02.rs) | `30.0ms` | `40.0ms` | `232 B` | `3 (2.0 KiB)` |",
+            "| [Day 4](./src/bin/2023-04.rs) | `40.0ms` | `50.0ms` | `-` | `-` |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table 2023 --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn keeps_tables_of_other_years() {
+        let other = "<!--- benchmarking table 2022 --->\n## 2022 Benchmarks\n<!--- benchmarking table 2022 --->";
+        let mut s = format!("{other}\n{MARKER}{MARKER}");
+        update_content(&mut s, YEAR, get_mock_timings(), 190.0).unwrap();
+        assert!(s.starts_with(other));
+        assert_eq!(s.matches("## 2023 Benchmarks").count(), 1);
+    }
 }
@@ -0,0 +1,106 @@
+//! Runs solutions in-process instead of spawning their binaries.
+//!
+//! The `solution!` macro implements [`Solution`] for every day. With the `registry` feature, a
+//! build script links all solutions in `src/bin` into the main binary as modules, so the `all`
+//! and `time` commands can call them directly. Days that are not in the registry, e.g. when the
+//! main binary is built without the feature, still run as separate binaries.
+use std::fmt::Debug;
+
+use crate::template::runner::RunOptions;
+use crate::template::PuzzleId;
+
+pub use crate::template::report::{PartReport, Stage, Status};
+
+/// A solution that can be run in-process.
+pub trait Solution: Sync {
+    /// The puzzle this solution solves.
+    fn puzzle(&self) -> PuzzleId;
+
+    /// Run the solution against `input`, printing results like its binary does, and return a
+    /// report for every stage that was executed. Panics are caught and reported per stage.
+    fn run(&self, input: &str, options: &RunOptions) -> Vec<PartReport>;
+}
+
+impl Debug for dyn Solution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Solution({})", self.puzzle())
+    }
+}
+
+/// The solutions linked into the main binary.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Registry {
+    solutions: &'static [&'static dyn Solution],
+}
+
+impl Registry {
+    pub const fn new(solutions: &'static [&'static dyn Solution]) -> Self {
+        Self { solutions }
+    }
+
+    /// Returns the solution of a puzzle, if it is linked into the main binary.
+    pub fn get(&self, puzzle: PuzzleId) -> Option<&'static dyn Solution> {
+        self.solutions
+            .iter()
+            .find(|solution| solution.puzzle() == puzzle)
+            .copied()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartReport, Registry, Solution, Stage, Status};
+    use crate::template::runner::{solve_part, RunOptions};
+    use crate::template::PuzzleId;
+    use crate::{day, year};
+
+    struct Mock;
+
+    const PUZZLE: PuzzleId = PuzzleId::new(year!(2023), day!(1));
+
+    impl Solution for Mock {
+        fn puzzle(&self) -> PuzzleId {
+            PUZZLE
+        }
+
+        fn run(&self, input: &str, options: &RunOptions) -> Vec<PartReport> {
+            let part_one = |input: &str| input.parse::<u32>().ok();
+            let part_two = |_: &str| -> Option<u32> { panic!("not yet") };
+            [
+                solve_part(part_one, input, PUZZLE, 1, options),
+                solve_part(part_two, input, PUZZLE, 2, options),
+            ]
+            .into_iter()
+            .flatten()
+            .collect()
+        }
+    }
+
+    static SOLUTIONS: [&dyn Solution; 1] = [&Mock];
+
+    #[test]
+    fn finds_solutions_by_puzzle() {
+        let registry = Registry::new(&SOLUTIONS);
+        assert!(registry.get(PUZZLE).is_some());
+        assert!(registry.get(PuzzleId::new(year!(2022), day!(1))).is_none());
+    }
+
+    #[test]
+    fn reports_results_and_panics() {
+        let reports = Mock.run("42", &RunOptions::default());
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].stage, Stage::Part(1));
+        assert_eq!(reports[0].status, Status::Solved);
+        assert_eq!(reports[0].answer, Some("42".into()));
+        assert_eq!(reports[1].status, Status::Panicked("not yet".into()));
+
+        let options = RunOptions {
+            part: Some(1),
+            ..RunOptions::default()
+        };
+        assert_eq!(Mock.run("x", &options)[0].status, Status::Unsolved);
+        assert_eq!(Mock.run("x", &options).len(), 1);
+    }
+}
@@ -0,0 +1,304 @@
+//! Machine-readable side channel between day binaries and the multi-day runner.
+//!
+//! When a day binary is invoked with `--report <path>`, every executed stage appends one JSON
+//! record per line to `<path>`. The human-readable output on stdout is not parsed by anything
+//! and can change freely.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{stats::BenchStats, Day};
+
+/// Outcome of running a single stage of a solution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The stage returned `Some(value)`.
+    Solved,
+    /// The stage returned `None`.
+    Unsolved,
+    /// The stage panicked with a message.
+    Panicked(String),
+}
+
+impl Status {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Panicked(_) => "panicked",
+        }
+    }
+}
+
+/// Stage of a solution a report refers to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    /// The optional parse stage shared by both parts.
+    Parse,
+    /// Part 1 or 2 of the solution.
+    Part(u8),
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part(part) => write!(f, "part_{part}"),
+        }
+    }
+}
+
+impl FromStr for Stage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "parse" {
+            return Ok(Stage::Parse);
+        }
+
+        s.strip_prefix("part_")
+            .and_then(|part| part.parse().ok())
+            .map(Stage::Part)
+            .ok_or(format!("Unknown report stage `{s}`."))
+    }
+}
+
+/// Result and execution time of a single stage of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub day: Day,
+    pub stage: Stage,
+    pub status: Status,
+    pub answer: Option<String>,
+    /// Execution time in nanoseconds. The median of all samples when benchmarked.
+    pub nanos: f64,
+    pub samples: u64,
+    /// Statistics of all samples, present when benchmarked.
+    pub stats: Option<BenchStats>,
+}
+
+/// Returns the report path passed to the current process via `--report <path>`, if any.
+pub fn report_path_from_args() -> Option<String> {
+    let args: Vec<String> = std::env::args().collect();
+    let index = args.iter().position(|x| x == "--report")?;
+    args.get(index + 1).cloned()
+}
+
+/// Append a report as a single JSON line to the file at `path`.
+pub fn append(path: &Path, report: &PartReport) -> Result<(), io::Error> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let line = JsonValue::from(report)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+    writeln!(file, "{line}")
+}
+
+/// Read all reports from the file at `path`. A missing file yields no reports.
+pub fn read_all(path: &Path) -> Result<Vec<PartReport>, String> {
+    match fs::read_to_string(path) {
+        Ok(s) => parse_lines(&s),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Parse JSON lines into reports, skipping blank lines.
+pub fn parse_lines(s: &str) -> Result<Vec<PartReport>, String> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let json = JsonValue::from_str(l).or(Err("Report is not valid JSON."))?;
+            PartReport::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("stage".into(), JsonValue::String(value.stage.to_string()));
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        if let Status::Panicked(message) = &value.status {
+            map.insert("panic".into(), JsonValue::String(message.clone()));
+        }
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "stats".into(),
+            match &value.stats {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartReport {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected report.day to be a Day struct.")?;
+
+        let stage = json
+            .get("stage")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected report.stage to be a string.")?
+            .parse()?;
+
+        let status = match json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected report.status to be a string.")?
+            .as_str()
+        {
+            "solved" => Status::Solved,
+            "unsolved" => Status::Unsolved,
+            "panicked" => Status::Panicked(
+                json.get("panic")
+                    .and_then(|v| v.get::<String>())
+                    .cloned()
+                    .unwrap_or_default(),
+            ),
+            s => return Err(format!("Unknown report status `{s}`.")),
+        };
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected report.nanos to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>().copied())
+            .map(|s| s as u64)
+            .ok_or("Expected report.samples to be a number.")?;
+
+        let stats = match json.get("stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(BenchStats::try_from(v)?),
+        };
+
+        Ok(PartReport {
+            day,
+            stage,
+            status,
+            answer: answer.cloned(),
+            nanos,
+            samples,
+            stats,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_lines, PartReport, Stage, Status};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_answers_with_special_characters() {
+        let report = PartReport {
+            day: day!(1),
+            stage: Stage::Part(2),
+            status: Status::Solved,
+            answer: Some("(42) @ 3\n\"quoted\"".into()),
+            nanos: 74130.0,
+            samples: 100,
+            stats: None,
+        };
+        let line = JsonValue::from(&report).stringify().unwrap();
+        assert!(!line.contains('\n'));
+        let parsed = parse_lines(&line).unwrap();
+        assert_eq!(parsed, vec![report]);
+    }
+
+    #[test]
+    fn roundtrips_panics() {
+        let report = PartReport {
+            day: day!(1),
+            stage: Stage::Part(1),
+            status: Status::Panicked("index out of bounds".into()),
+            answer: None,
+            nanos: 10.0,
+            samples: 1,
+            stats: None,
+        };
+        let line = JsonValue::from(&report).stringify().unwrap();
+        assert_eq!(parse_lines(&line).unwrap(), vec![report]);
+    }
+
+    #[test]
+    fn parses_multiple_lines() {
+        let s = [
+            r#"{ "day": "03", "stage": "parse", "status": "solved", "answer": null, "nanos": 4, "samples": 1 }"#,
+            r#"{ "day": "03", "stage": "part_1", "status": "solved", "answer": "1", "nanos": 10, "samples": 1 }"#,
+            "",
+            r#"{ "day": "03", "stage": "part_2", "status": "unsolved", "answer": null, "nanos": 5, "samples": 1 }"#,
+        ]
+        .join("\n");
+        let parsed = parse_lines(&s).unwrap();
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0].stage, Stage::Parse);
+        assert_eq!(parsed[1].stage, Stage::Part(1));
+        assert_eq!(parsed[1].answer, Some("1".into()));
+        assert_eq!(parsed[2].status, Status::Unsolved);
+        assert_eq!(parsed[2].answer, None);
+    }
+
+    #[test]
+    fn roundtrips_stages() {
+        for stage in [Stage::Parse, Stage::Part(1), Stage::Part(2), Stage::Part(3)] {
+            assert_eq!(stage.to_string().parse(), Ok(stage));
+        }
+        assert_eq!(Stage::Part(1).to_string(), "part_1");
+        assert!("part_x".parse::<Stage>().is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_reports() {
+        parse_lines(r#"{ "day": "03", "stage": "part_3" }"#).unwrap();
+    }
+}
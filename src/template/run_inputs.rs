@@ -0,0 +1,237 @@
+//! Runs a solution against a set of inputs and checks the answers against a manifest of
+//! expected answers.
+//!
+//! This covers two sets of inputs:
+//!  1. every file in a day's inputs directory, e.g. the inputs of teammates in
+//!     `data/2023/inputs/05/alice.txt`, checked against the optional `answers.json` manifest in
+//!     the same directory.
+//!  2. every example listed in a day's example manifest, e.g. `data/2023/examples/05.json`.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{
+    answers::Verdict,
+    manifest::Manifest,
+    report::{PartReport, Stage, Status},
+    run_multi::{child_commands, Error},
+    PuzzleId, ANSI_BOLD, ANSI_RESET,
+};
+
+static MANIFEST_FILE_NAME: &str = "answers.json";
+
+/// Outcome of running a solution against a set of inputs.
+#[derive(Debug, Default)]
+pub struct InputsRun {
+    /// Number of inputs that were run.
+    pub inputs: usize,
+    /// Names of inputs that failed to run or produced a wrong answer.
+    pub failed: Vec<String>,
+}
+
+/// Returns all `.txt` files in the inputs directory of a puzzle, sorted by name.
+fn list_inputs(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut inputs: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+
+    inputs.sort();
+    Ok(inputs)
+}
+
+/// Run a solution against every input in its inputs directory.
+pub fn run_inputs(puzzle: PuzzleId, is_release: bool) -> Result<InputsRun, Error> {
+    let dir = PathBuf::from(puzzle.inputs_dir());
+    let inputs: Vec<(String, PathBuf)> = list_inputs(&dir)?
+        .into_iter()
+        .map(|path| {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            (name, path)
+        })
+        .collect();
+    let manifest =
+        Manifest::read_from_file(&dir.join(MANIFEST_FILE_NAME)).map_err(Error::Manifest)?;
+
+    run_all(puzzle, &inputs, &manifest, is_release)
+}
+
+/// Run a solution against every example listed in its example manifest.
+pub fn run_examples(puzzle: PuzzleId, is_release: bool) -> Result<InputsRun, Error> {
+    let manifest = Manifest::read_from_file(Path::new(&puzzle.example_manifest_path()))
+        .map_err(Error::Manifest)?;
+
+    let dir = PathBuf::from(format!("{}/examples", puzzle.year.data_dir()));
+    let inputs: Vec<(String, PathBuf)> = manifest
+        .data
+        .iter()
+        .map(|e| (e.input.clone(), dir.join(&e.input)))
+        .collect();
+
+    run_all(puzzle, &inputs, &manifest, is_release)
+}
+
+fn run_all(
+    puzzle: PuzzleId,
+    inputs: &[(String, PathBuf)],
+    manifest: &Manifest,
+    is_release: bool,
+) -> Result<InputsRun, Error> {
+    let mut run = InputsRun::default();
+
+    for (name, path) in inputs {
+        println!();
+        println!("{ANSI_BOLD}Input {name}{ANSI_RESET}");
+
+        run.inputs += 1;
+
+        // inputs that only apply to one part, e.g. a second example, only run that part.
+        let expected_parts: Vec<u8> = [1, 2]
+            .into_iter()
+            .filter(|part| manifest.get(name, *part).is_some())
+            .collect();
+        let only_part = match expected_parts[..] {
+            [part] => Some(part),
+            _ => None,
+        };
+
+        let reports =
+            match child_commands::run_solution(puzzle, Some(path), only_part, false, is_release) {
+                Ok(reports) => reports,
+                Err(e) => {
+                    eprintln!("Failed to run input {name}: {e}");
+                    run.failed.push(name.clone());
+                    continue;
+                }
+            };
+
+        if !check_reports(name, &reports, manifest) {
+            run.failed.push(name.clone());
+        }
+    }
+
+    Ok(run)
+}
+
+/// Print the verdict of every part for an input and return `true` if the input passed.
+/// An input fails if any stage panicked or parsing failed, even without expected answers.
+fn check_reports(name: &str, reports: &[PartReport], manifest: &Manifest) -> bool {
+    if reports.is_empty() {
+        println!("No results.");
+        return false;
+    }
+
+    let mut is_passed = true;
+
+    if let Some(parse) = reports.iter().find(|r| r.stage == Stage::Parse) {
+        match &parse.status {
+            Status::Solved => {}
+            Status::Unsolved => {
+                println!("Parse: ✘ failed");
+                is_passed = false;
+            }
+            Status::Panicked(message) => {
+                println!("Parse: ✘ panicked ({message})");
+                is_passed = false;
+            }
+        }
+    }
+
+    for part in [1, 2] {
+        let report = reports.iter().find(|r| r.stage == Stage::Part(part));
+        let answer = report
+            .filter(|r| r.status == Status::Solved)
+            .and_then(|r| r.answer.as_deref());
+
+        // parts that did not run and have no expected answer are not worth mentioning.
+        if report.is_none() && manifest.get(name, part).is_none() {
+            continue;
+        }
+
+        if let Some(Status::Panicked(message)) = report.map(|r| &r.status) {
+            println!("Part {part}: ✘ panicked ({message})");
+            is_passed = false;
+            continue;
+        }
+
+        let verdict = manifest.verify(name, part, answer);
+        if matches!(verdict, Verdict::Wrong { .. }) {
+            is_passed = false;
+        }
+
+        println!("Part {part}: {verdict}");
+    }
+
+    is_passed
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::check_reports;
+    use crate::day;
+    use crate::template::{
+        manifest::Manifest,
+        report::{PartReport, Stage, Status},
+    };
+
+    fn report(stage: Stage, status: Status, answer: Option<&str>) -> PartReport {
+        PartReport {
+            day: day!(5),
+            stage,
+            status,
+            answer: answer.map(Into::into),
+            nanos: 10.0,
+            samples: 1,
+            stats: None,
+        }
+    }
+
+    fn get_mock_manifest() -> Manifest {
+        let json = r#"{ "data": [{ "input": "alice.txt", "part_1": "35" }] }"#;
+        Manifest::try_from(json.to_string()).unwrap()
+    }
+
+    #[test]
+    fn passes_matching_and_unknown_answers() {
+        let reports = [
+            report(Stage::Part(1), Status::Solved, Some("35")),
+            report(Stage::Part(2), Status::Solved, Some("46")),
+        ];
+        assert!(check_reports("alice.txt", &reports, &get_mock_manifest()));
+        assert!(check_reports("bob.txt", &reports, &get_mock_manifest()));
+    }
+
+    #[test]
+    fn fails_panics_without_expected_answers() {
+        let reports = [
+            report(Stage::Part(1), Status::Solved, Some("1")),
+            report(Stage::Part(2), Status::Panicked("overflow".into()), None),
+        ];
+        assert!(!check_reports("bob.txt", &reports, &get_mock_manifest()));
+    }
+
+    #[test]
+    fn fails_failed_parse_stages() {
+        let manifest = get_mock_manifest();
+        let panicked = [report(Stage::Parse, Status::Panicked("oops".into()), None)];
+        assert!(!check_reports("bob.txt", &panicked, &manifest));
+        let unsolved = [report(Stage::Parse, Status::Unsolved, None)];
+        assert!(!check_reports("bob.txt", &unsolved, &manifest));
+    }
+
+    #[test]
+    fn fails_inputs_without_reports() {
+        assert!(!check_reports("bob.txt", &[], &get_mock_manifest()));
+    }
+}
@@ -1,14 +1,33 @@
-use std::{collections::HashSet, io};
+use std::{collections::HashSet, fs, io, process::ExitStatus};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    registry::Registry, runner::RunOptions, Day, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
+    report::PartReport,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Outcome of running a set of days.
+pub struct MultiRun {
+    /// Benchmark times, present for timed runs.
+    pub timings: Option<Timings>,
+    /// Reports of all stages that were executed.
+    pub reports: Vec<PartReport>,
+}
+
+/// Run a set of days. Days in the registry run in-process, all others run as separate binaries.
+pub fn run_multi(
+    registry: &Registry,
+    year: Year,
+    days_to_run: HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+) -> MultiRun {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut all_reports: Vec<PartReport> = vec![];
 
     all_days().for_each(|day| {
         if day > 1 {
@@ -18,23 +37,49 @@ pub fn run_multi(days_to_run: HashSet<Day>, is_release: bool, is_timed: bool) ->
         println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
         println!("------");
 
+        let puzzle = PuzzleId::new(year, day);
+
         if !days_to_run.contains(&day) {
             println!("Skipped.");
             return;
         }
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let reports = match registry.get(puzzle) {
+            Some(solution) => match fs::read_to_string(puzzle.input_path()) {
+                Ok(input) => {
+                    let options = RunOptions {
+                        is_timed,
+                        part: None,
+                    };
+                    solution.run(&input, &options)
+                }
+                Err(e) => {
+                    eprintln!("Failed to read input of day {day}: {e}");
+                    return;
+                }
+            },
+            None => match child_commands::run_solution(puzzle, None, None, is_timed, is_release) {
+                Ok(reports) => reports,
+                Err(e) => {
+                    eprintln!("Failed to run day {day}: {e}");
+                    return;
+                }
+            },
+        };
 
-        if output.is_empty() {
+        if reports.is_empty() {
             println!("Not solved.");
         } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+            timings.push(child_commands::timing_from_reports(&reports, day));
+            all_reports.extend(reports);
         }
     });
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    let timings = if is_timed {
+        let timings = Timings {
+            data: timings,
+            history: vec![],
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
@@ -42,13 +87,21 @@ pub fn run_multi(days_to_run: HashSet<Day>, is_release: bool, is_timed: bool) ->
         Some(timings)
     } else {
         None
+    };
+
+    MultiRun {
+        timings,
+        reports: all_reports,
     }
 }
 
 #[derive(Debug)]
 pub enum Error {
-    BrokenPipe,
     IO(io::Error),
+    Report(String),
+    Manifest(String),
+    Profile(String),
+    Exit(ExitStatus),
 }
 
 impl From<std::io::Error> for Error {
@@ -57,134 +110,179 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::IO(e) => write!(f, "could not run solution: {e}"),
+            Error::Report(e) => write!(f, "could not read solution report: {e}"),
+            Error::Manifest(e) => write!(f, "could not read expected answers: {e}"),
+            Error::Profile(e) => write!(f, "could not read heap profile: {e}"),
+            Error::Exit(status) => write!(f, "solution exited with {status}"),
+        }
+    }
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting the reports they write.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::{
+        memory::MemoryStats,
+        report::{self, PartReport, Stage, Status},
+        timings::{StageTiming, Timing},
+        Day, PuzzleId,
+    };
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
+        env, fs,
+        path::{Path, PathBuf},
+        process::{self, Command, Stdio},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given puzzle and collect the reports of all executed parts.
+    /// `input` overrides the puzzle input and `part` restricts the run to a single part, if set.
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        input: Option<&Path>,
+        part: Option<u8>,
+        is_timed: bool,
+        is_release: bool,
+    ) -> Result<Vec<PartReport>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !Path::new(&puzzle.bin_path()).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let report_path = get_report_path(puzzle);
+        // a stale report from an aborted run would otherwise be read as part of this run.
+        let _ = fs::remove_file(&report_path);
+
+        let bin_name = puzzle.to_string();
+        let report_path_str = report_path.to_string_lossy().to_string();
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
 
         if is_release {
             args.push("--release");
         }
 
+        args.push("--");
+        args.push("--report");
+        args.push(&report_path_str);
+
+        let input_str = input.map(|path| path.to_string_lossy().to_string());
+        if let Some(input_str) = &input_str {
+            args.push("--input");
+            args.push(input_str);
+        }
+
+        let part_str = part.map(|part| part.to_string());
+        if let Some(part_str) = &part_str {
+            args.push("--part");
+            args.push(part_str);
+        }
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
-
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
-
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let mut cmd = Command::new("cargo");
+        cmd.args(&args);
+        collect_reports(&mut cmd, &report_path)
+    }
 
-        let mut output = vec![];
+    /// Run an already built solution binary from `dir`, e.g. a checkout of another revision,
+    /// and collect the reports of all executed parts.
+    pub fn run_binary(
+        puzzle: PuzzleId,
+        bin: &Path,
+        dir: &Path,
+        is_timed: bool,
+    ) -> Result<Vec<PartReport>, Error> {
+        let report_path = get_report_path(puzzle);
+        let _ = fs::remove_file(&report_path);
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        let mut cmd = Command::new(bin);
+        cmd.current_dir(dir).arg("--report").arg(&report_path);
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        if is_timed {
+            cmd.arg("--time");
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
-
-        Ok(output)
+        collect_reports(&mut cmd, &report_path)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    /// Run the solution bin for a given puzzle with the `dhat-heap` feature and read the heap
+    /// profile it writes.
+    pub fn run_heap_profile(puzzle: PuzzleId) -> Result<MemoryStats, Error> {
+        let profile_path = puzzle.dhat_path();
+        // a stale profile from an earlier run would otherwise be read as part of this run.
+        let _ = fs::remove_file(&profile_path);
+
+        let bin_name = puzzle.to_string();
+        Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--profile",
+                "dhat",
+                "--features",
+                "dhat-heap",
+                "--bin",
+                &bin_name,
+            ])
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()?;
+
+        MemoryStats::read_dhat_file(&profile_path).map_err(Error::Profile)
+    }
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+    fn collect_reports(cmd: &mut Command, report_path: &Path) -> Result<Vec<PartReport>, Error> {
+        // human-readable output is forwarded as-is, results are read from the report file.
+        let status = cmd
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()?;
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+        let reports = report::read_all(report_path).map_err(Error::Report);
+        let _ = fs::remove_file(report_path);
 
-                timings.total_nanos += nanos;
-            });
+        // panics in a part are caught and reported, so a failed exit means the solution didn't
+        // build or couldn't read its input.
+        if !status.success() {
+            return Err(Error::Exit(status));
+        }
 
-        timings
+        reports
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    fn get_report_path(puzzle: PuzzleId) -> PathBuf {
+        env::temp_dir().join(format!("aoc-report-{}-{puzzle}.jsonl", process::id()))
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    /// Build the timing for a day from the reports of its stages.
+    pub fn timing_from_reports(reports: &[PartReport], day: Day) -> Timing {
+        let mut timing = Timing::new(day);
+
+        reports
+            .iter()
+            .filter(|r| r.day == day && r.status == Status::Solved)
+            .for_each(|r| {
+                let stage = Some(StageTiming {
+                    nanos: r.nanos,
+                    samples: r.samples,
+                    stats: r.stats.clone(),
+                });
+
+                match r.stage {
+                    Stage::Parse => timing.parse = stage,
+                    Stage::Part(1) => timing.part_1 = stage,
+                    Stage::Part(2) => timing.part_2 = stage,
+                    Stage::Part(_) => {}
+                }
+            });
+
+        timing
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -203,51 +301,87 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::timing_from_reports;
 
         use crate::day;
+        use crate::template::report::{PartReport, Stage, Status};
+
+        fn report(part: u8, status: Status, answer: Option<&str>, nanos: f64) -> PartReport {
+            PartReport {
+                day: day!(1),
+                stage: if part == 0 {
+                    Stage::Parse
+                } else {
+                    Stage::Part(part)
+                },
+                status,
+                answer: answer.map(Into::into),
+                nanos,
+                samples: 100,
+                stats: None,
+            }
+        }
+
+        #[test]
+        fn builds_timing_from_reports() {
+            let res = timing_from_reports(
+                &[
+                    report(1, Status::Solved, Some("0"), 74.13),
+                    report(2, Status::Solved, Some("10"), 74130000.0),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos(), 74130074.13_f64);
+            assert_eq!(res.part_1.as_ref().unwrap().samples, 100);
+            assert_eq!(res.part_1.unwrap().formatted(), "74.13ns");
+            assert_eq!(res.part_2.unwrap().formatted(), "74.1ms");
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn handles_patterns_in_answers() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    report(
+                        1,
+                        Status::Solved,
+                        Some("@ @ @ ( ) ms (2s @ 5 samples)"),
+                        2e9,
+                    ),
+                    report(2, Status::Solved, Some("line 1\nline 2"), 1e8),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos(), 2100000000_f64);
+            assert_eq!(res.part_1.unwrap().formatted(), "2.0s");
+            assert_eq!(res.part_2.unwrap().formatted(), "100.0ms");
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn includes_parse_stage() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    report(0, Status::Solved, None, 1000.0),
+                    report(1, Status::Solved, Some("1"), 2000.0),
+                    report(2, Status::Solved, Some("2"), 3000.0),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.total_nanos(), 6000_f64);
+            assert_eq!(res.parse.unwrap().formatted(), "1.0µs");
+            assert_eq!(res.part_1.unwrap().formatted(), "2.0µs");
+            assert_eq!(res.part_2.unwrap().formatted(), "3.0µs");
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
+        fn handles_missing_parts() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    report(1, Status::Unsolved, None, 10.0),
+                    report(2, Status::Unsolved, None, 10.0),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_approx_eq!(res.total_nanos(), 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
@@ -1,70 +1,361 @@
-/// Encapsulates code that interacts with solution functions.
-use std::fmt::Display;
+//! Encapsulates code that interacts with solution functions.
+use std::any::Any;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{self, stdout, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process};
 
+use crate::template::alloc::{self, AllocStats};
+use crate::template::answer::Answer;
+use crate::template::answers::Answers;
+use crate::template::aoc_client::{AocClient, Backend};
+use crate::template::report::{self, PartReport, Stage, Status};
+use crate::template::stats::BenchStats;
+use crate::template::submissions::{ServerVerdict, Submissions};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, PuzzleId, ANSI_ITALIC, ANSI_RESET};
+
+/// Where a solution reads its input from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input, e.g. `data/2023/inputs/05.txt`.
+    #[default]
+    Puzzle,
+    /// A file passed via `--input <path>`.
+    File(String),
+    /// Standard input, passed via `--input -`.
+    Stdin,
+    /// An example passed via `--example [n]`, e.g. `data/2023/examples/05.txt` or `05-2.txt`.
+    Example(Option<u8>),
+}
+
+impl InputSource {
+    /// Read the input source passed to a solution binary via `--input <path>` or `--example [n]`.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        Self::parse_args(&args)
+    }
+
+    fn parse_args(args: &[String]) -> Self {
+        let value_of = |flag: &str| {
+            let index = args.iter().position(|x| x == flag)?;
+            Some(args.get(index + 1))
+        };
+
+        if let Some(path) = value_of("--input").flatten() {
+            return Self::from_path(path);
+        }
+
+        match value_of("--example") {
+            Some(n) => Self::Example(n.and_then(|n| n.parse().ok())),
+            None => Self::Puzzle,
+        }
+    }
+
+    /// An input file, or standard input for `-`.
+    pub fn from_path(path: &str) -> Self {
+        if path == "-" {
+            Self::Stdin
+        } else {
+            Self::File(path.to_string())
+        }
+    }
+
+    /// The arguments that select this input source in a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Puzzle => vec![],
+            Self::File(path) => vec!["--input".into(), path.clone()],
+            Self::Stdin => vec!["--input".into(), "-".into()],
+            Self::Example(None) => vec!["--example".into()],
+            Self::Example(Some(n)) => vec!["--example".into(), n.to_string()],
+        }
+    }
+
+    /// Returns `true` for the puzzle input, whose answers can be submitted and verified.
+    pub fn is_puzzle(&self) -> bool {
+        matches!(self, Self::Puzzle)
+    }
+
+    /// The path of the input file, if the input is read from a file.
+    pub fn path(&self, puzzle: PuzzleId) -> Option<String> {
+        match self {
+            Self::Puzzle => Some(puzzle.input_path()),
+            Self::File(path) => Some(path.clone()),
+            Self::Stdin => None,
+            Self::Example(None) => Some(puzzle.example_path()),
+            Self::Example(Some(n)) => Some(format!(
+                "{}/examples/{}-{n}.txt",
+                puzzle.year.data_dir(),
+                puzzle.day
+            )),
+        }
+    }
+
+    /// Read the input. Exits if it can't be read.
+    pub fn read(&self, puzzle: PuzzleId) -> String {
+        let result = match self.path(puzzle) {
+            Some(path) => fs::read_to_string(path),
+            None => io::read_to_string(io::stdin()),
+        };
+
+        result.unwrap_or_else(|e| {
+            match self.path(puzzle) {
+                Some(path) => eprintln!("Failed to read input from \"{path}\": {e}"),
+                None => eprintln!("Failed to read input from stdin: {e}"),
+            }
+            process::exit(1);
+        })
+    }
+}
+
+/// Read the input of a solution binary: the puzzle input, or the input selected via
+/// `--input <path>`, `--input -` or `--example [n]`.
+pub fn read_input(puzzle: PuzzleId) -> String {
+    InputSource::from_args().read(puzzle)
+}
+
+/// Start profiling the heap of a solution binary built with `dhat-heap`. The profile covers
+/// everything after reading the input and is written to [`PuzzleId::dhat_path`] when the
+/// returned profiler is dropped at the end of `main`.
+#[cfg(feature = "dhat-heap")]
+pub fn start_heap_profiler(puzzle: PuzzleId) -> dhat::Profiler {
+    let path = puzzle.dhat_path();
+    if let Some(dir) = Path::new(&path).parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    dhat::Profiler::builder().file_name(path).build()
+}
+
+/// Options for running a solution, taken from the command line for solution binaries.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    /// Benchmark each stage instead of running it once.
+    pub is_timed: bool,
+    /// Only run this part, if set.
+    pub part: Option<u8>,
+}
+
+impl RunOptions {
+    /// Read the options passed to a solution binary via `--time` and `--part <n>`.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        let part = args
+            .iter()
+            .position(|x| x == "--part")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|part| part.parse().ok());
+
+        Self {
+            is_timed: args.iter().any(|x| x == "--time"),
+            part,
+        }
+    }
+
+    fn is_part_selected(&self, part: u8) -> bool {
+        self.part.is_none_or(|selected| selected == part)
+    }
+}
+
+/// Run a part of a solution binary, then write its report and submit its answer if requested.
+pub fn run_part<I: Clone, T: Into<Answer>>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+) {
+    let Some(report) = solve_part(func, input, puzzle, part, &RunOptions::from_args()) else {
+        return;
+    };
+
+    write_report(&report);
+
+    if let (Status::Solved, Some(answer)) = (&report.status, &report.answer) {
+        submit_result(answer, puzzle, part);
+    }
+}
+
+/// Run a part of a solution, print its result and return its report.
+/// Returns [`None`] if a different part was selected.
+///
+/// The report contains the answer in the form it is submitted in, see [`Answer::to_submission`].
+pub fn solve_part<I: Clone, T: Into<Answer>>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+    options: &RunOptions,
+) -> Option<PartReport> {
+    if !options.is_part_selected(part) {
+        return None;
+    }
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let func = |input: I| func(input).map(Into::into);
+    let (result, duration, stats) = run_timed(func, input, options.is_timed, |result| {
+        print_result(result, &part_str, "");
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let samples = stats.as_ref().map_or(1, |s| s.samples);
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    let (status, answer) = match result {
+        Ok((result, allocations)) => {
+            let timing = format_duration(&duration, samples) + &format_allocations(&allocations);
+            print_result(&result, &part_str, &timing);
+            let answer = result
+                .as_ref()
+                .map(|answer| answer.to_submission().unwrap_or_else(|| answer.to_string()));
+            (status_of(answer.is_some()), answer)
+        }
+        Err(message) => {
+            print_panic(&part_str, &message);
+            (Status::Panicked(message), None)
+        }
+    };
+
+    if let Some(stats) = &stats {
+        println!("  {ANSI_ITALIC}{stats}{ANSI_RESET}");
+    }
+
+    Some(new_report(
+        puzzle,
+        Stage::Part(part),
+        status,
+        answer,
+        &duration,
+        stats,
+    ))
+}
+
+/// Run the optional parse stage of a solution binary, whose output is shared by both parts.
+/// Returns [`None`] and skips both parts if parsing fails.
+pub fn run_parse<P>(func: impl Fn(&str) -> Option<P>, input: &str, puzzle: PuzzleId) -> Option<P> {
+    let (parsed, report) = solve_parse(func, input, puzzle, &RunOptions::from_args());
+    write_report(&report);
+    parsed
+}
+
+/// Run the optional parse stage of a solution, print its result and return its report.
+pub fn solve_parse<P>(
+    func: impl Fn(&str) -> Option<P>,
+    input: &str,
+    puzzle: PuzzleId,
+    options: &RunOptions,
+) -> (Option<P>, PartReport) {
+    let (result, duration, stats) = run_timed(func, input, options.is_timed, |_| {
+        print!("Parse: ");
+    });
+
+    let samples = stats.as_ref().map_or(1, |s| s.samples);
+    print!("\r");
+
+    let (parsed, status) = match result {
+        Ok((Some(parsed), allocations)) => {
+            println!(
+                "Parse:{}{}",
+                format_duration(&duration, samples),
+                format_allocations(&allocations)
+            );
+            (Some(parsed), Status::Solved)
+        }
+        Ok((None, _)) => {
+            println!("Parse: ✖             ");
+            (None, Status::Unsolved)
+        }
+        Err(message) => {
+            print_panic("Parse", &message);
+            (None, Status::Panicked(message))
+        }
+    };
+
+    if let Some(stats) = &stats {
+        println!("  {ANSI_ITALIC}{stats}{ANSI_RESET}");
     }
+
+    let report = new_report(puzzle, Stage::Parse, status, None, &duration, stats);
+    (parsed, report)
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+fn status_of(is_solved: bool) -> Status {
+    if is_solved {
+        Status::Solved
+    } else {
+        Status::Unsolved
+    }
+}
+
+/// Returns the message of a panic payload.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".into())
+}
+
+/// Run a solution part. The behavior differs depending on `is_timed`:
+///  1. if not timed, the function is executed once.
+///  2. if timed, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// When benched, the returned duration is the median of all samples.
+/// A panic in the first execution is caught and returned as an error with its message.
+/// The allocations of the first execution are returned with its result, see [`alloc`].
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (
+    Result<(T, AllocStats), String>,
+    Duration,
+    Option<BenchStats>,
+) {
     let timer = Instant::now();
-    let result = {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
         let input = input.clone();
+        alloc::measure(|| func(input))
+    }));
+    let base_time = timer.elapsed();
 
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
-
-        func(input)
+    let result = match result {
+        Ok(result) => result,
+        Err(payload) => return (Err(panic_message(&*payload)), base_time, None),
     };
-    let base_time = timer.elapsed();
 
-    hook(&result);
+    hook(&result.0);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let stats = if is_timed {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        None
     };
 
-    (result, run.0, run.1)
+    let duration = stats.as_ref().map_or(base_time, |s| {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Duration::from_nanos(s.median.round() as u64)
+    });
+
+    (Ok(result), duration, stats)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Option<BenchStats> {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+
+    // the first iterations warm up caches and the branch predictor and are not measured.
+    let warmup_iterations = cmp::max(bench_iterations / 10, 1);
+
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input.clone())));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -76,22 +367,13 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
+    BenchStats::from_samples(
+        &timers,
+        u64::try_from(warmup_iterations).unwrap_or(u64::MAX),
     )
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
-}
-
-fn format_duration(duration: &Duration, samples: u128) -> String {
+fn format_duration(duration: &Duration, samples: u64) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
     } else {
@@ -99,13 +381,27 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// The allocations of a stage, shown next to its duration with the `count-allocs` feature.
+fn format_allocations(allocations: &AllocStats) -> String {
+    if alloc::IS_ENABLED {
+        format!(" · {allocations}")
+    } else {
+        String::new()
+    }
+}
+
+fn print_result(result: &Option<Answer>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
         Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+            if result.is_multiline() {
+                // show the letters of block letter grids next to the marker.
+                let letters = result
+                    .to_submission()
+                    .map(|letters| format!("{ANSI_BOLD}{letters}{ANSI_RESET}"))
+                    .unwrap_or_default();
+                let str = format!("{part}: ▼ {letters}{duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -134,18 +430,55 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
+fn print_panic(part: &str, message: &str) {
+    print!("\r");
+    println!("{part}: ✖ panicked: {message}");
+}
+
+fn new_report(
+    puzzle: PuzzleId,
+    stage: Stage,
+    status: Status,
+    answer: Option<String>,
+    duration: &Duration,
+    stats: Option<BenchStats>,
+) -> PartReport {
+    #[allow(clippy::cast_precision_loss)]
+    PartReport {
+        day: puzzle.day,
+        stage,
+        status,
+        answer,
+        nanos: duration.as_nanos() as f64,
+        samples: stats.as_ref().map_or(1, |s| s.samples),
+        stats,
+    }
+}
+
+/// Append a machine-readable record of a stage to the file passed via `--report <path>`, if any.
+fn write_report(report: &PartReport) {
+    let Some(path) = report::report_path_from_args() else {
+        return;
+    };
+
+    if let Err(e) = report::append(Path::new(&path), report) {
+        eprintln!("Failed to write report to \"{path}\": {e}");
+    }
+}
+
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///  2. a session cookie is configured, or aoc-cli is installed when used as backend.
+///  3. the answer is not known to be wrong from earlier submissions, unless `--force` is passed.
+///  4. the answer has a submittable form, e.g. a grid must spell block letters.
+///  5. the solution ran against the puzzle input, not a file passed via `--input` or `--example`.
+///
+/// Every submission and the server's verdict are recorded in the submission ledger.
+fn submit_result(result: &str, puzzle: PuzzleId, part: u8) {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
-        return None;
+        return;
     }
 
     if args.len() < 3 {
@@ -161,14 +494,165 @@ fn submit_result<T: Display>(
     };
 
     if part_submit != part {
-        return None;
+        return;
     }
 
+    if !InputSource::from_args().is_puzzle() {
+        eprintln!("Refusing to submit: the answer is not for the puzzle input.");
+        process::exit(1);
+    }
+
+    let Some(answer) = Answer::parse(result).to_submission() else {
+        eprintln!("Refusing to submit: the answer is a grid that does not spell block letters.");
+        process::exit(1);
+    };
+    let mut submissions = Submissions::read_from_file(puzzle.year);
+
+    if let Err(refusal) = submissions.check(puzzle.day, part, &answer) {
+        if args.contains(&"--force".into()) {
+            println!("Submitting anyway: {refusal}");
+        } else {
+            eprintln!("Refusing to submit `{answer}`: {refusal} Pass `--force` to submit anyway.");
+            process::exit(1);
+        }
+    }
+
+    let response = match Backend::from_env() {
+        Backend::Native => submit_with_client(puzzle, part, &answer),
+        Backend::AocCli => submit_with_aoc_cli(puzzle, part, &answer),
+    };
+
+    if let Some(response) = response {
+        record_submission(&mut submissions, puzzle, part, &answer, &response);
+    }
+}
+
+/// Submit an answer with the built-in client. Returns the server's response, if any.
+fn submit_with_client(puzzle: PuzzleId, part: u8, answer: &str) -> Option<String> {
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to submit result: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!("Submitting result...");
+    match client.submit(puzzle, part, answer) {
+        Ok(response) => {
+            println!("{}", response.message);
+            Some(response.message)
+        }
+        Err(e) => {
+            eprintln!("Failed to submit result: {e}");
+            None
+        }
+    }
+}
+
+/// Submit an answer via aoc-cli. Returns the command's output, if it could be called.
+fn submit_with_aoc_cli(puzzle: PuzzleId, part: u8, answer: &str) -> Option<String> {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    match aoc_cli::submit(puzzle, part, answer) {
+        Ok(output) | Err(aoc_cli::AocCommandError::BadExitStatus(output)) => Some(format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        )),
+        Err(_) => None,
+    }
+}
+
+/// Store a submission and its verdict in the ledger. Correct answers are also stored as confirmed answers.
+fn record_submission(
+    submissions: &mut Submissions,
+    puzzle: PuzzleId,
+    part: u8,
+    answer: &str,
+    response: &str,
+) {
+    let verdict = ServerVerdict::from_response(response);
+    submissions.record(puzzle.day, part, answer, verdict);
+
+    if let Err(e) = submissions.store_file(puzzle.year) {
+        eprintln!("Failed to store submission: {e}");
+        return;
+    }
+
+    println!("Recorded submission as {ANSI_BOLD}{verdict}{ANSI_RESET}.");
+
+    if verdict == ServerVerdict::Correct {
+        let mut answers = Answers::read_from_file(puzzle.year);
+        answers.set(puzzle.day, part, answer);
+        if let Err(e) = answers.store_file(puzzle.year) {
+            eprintln!("Failed to store confirmed answer: {e}");
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::InputSource;
+    use crate::template::PuzzleId;
+    use crate::{day, year};
+
+    fn parse(args: &[&str]) -> InputSource {
+        let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+        InputSource::parse_args(&args)
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        assert_eq!(parse(&["bin"]), InputSource::Puzzle);
+        assert_eq!(
+            parse(&["bin", "--input", "alice.txt"]),
+            InputSource::File("alice.txt".into())
+        );
+        assert_eq!(parse(&["bin", "--input", "-"]), InputSource::Stdin);
+        assert_eq!(parse(&["bin", "--example"]), InputSource::Example(None));
+        assert_eq!(
+            parse(&["bin", "--example", "--time"]),
+            InputSource::Example(None)
+        );
+        assert_eq!(
+            parse(&["bin", "--example", "2"]),
+            InputSource::Example(Some(2))
+        );
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::File("alice.txt".into()),
+            InputSource::Stdin,
+            InputSource::Example(None),
+            InputSource::Example(Some(2)),
+        ] {
+            let mut args = vec!["bin".to_string()];
+            args.extend(source.to_args());
+            assert_eq!(InputSource::parse_args(&args), source);
+        }
+    }
+
+    #[test]
+    fn resolves_paths() {
+        let puzzle = PuzzleId::new(year!(2023), day!(5));
+        assert_eq!(
+            InputSource::Puzzle.path(puzzle),
+            Some("data/2023/inputs/05.txt".into())
+        );
+        assert_eq!(
+            InputSource::Example(Some(2)).path(puzzle),
+            Some("data/2023/examples/05-2.txt".into())
+        );
+        assert_eq!(InputSource::Stdin.path(puzzle), None);
+    }
 }
@@ -0,0 +1,315 @@
+//! Summary statistics for benchmark samples.
+use std::{collections::HashMap, fmt::Display, time::Duration};
+
+use tinyjson::JsonValue;
+
+/// Z-score of the two-sided 95% confidence interval.
+const Z_95: f64 = 1.96;
+
+/// Factor of the inter-quartile range that defines Tukey's fences.
+const TUKEY_FENCE: f64 = 1.5;
+
+/// Relative change of the mean below which a significant difference is still reported as
+/// unchanged. Runs on the same machine differ by a few percent due to frequency scaling and
+/// background load, which a t-test with thousands of samples readily flags as significant.
+const MIN_RELATIVE_CHANGE: f64 = 0.05;
+
+/// How the mean of a benchmark changed relative to a baseline, e.g. `0.1` for 10% slower.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Change {
+    Regression(f64),
+    Improvement(f64),
+    Unchanged(f64),
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::Regression(x) => write!(f, "+{:.1}% ▲ regression", x * 100.0),
+            Change::Improvement(x) => write!(f, "{:.1}% ▼ improvement", x * 100.0),
+            Change::Unchanged(x) => write!(f, "{:+.1}%", x * 100.0),
+        }
+    }
+}
+
+/// Statistical summary of benchmark samples.
+///
+/// Order statistics (min, median, percentiles) are computed over all samples. Mean, standard
+/// deviation and the confidence interval exclude outliers, which are detected with Tukey's
+/// fences (values further than 1.5 × IQR away from the first or third quartile).
+/// All durations are in nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchStats {
+    /// Number of measured samples, excluding warm-up iterations.
+    pub samples: u64,
+    /// Number of discarded warm-up iterations.
+    pub warmup: u64,
+    /// Number of samples outside of Tukey's fences.
+    pub outliers: u64,
+    pub min: f64,
+    pub median: f64,
+    pub p90: f64,
+    pub p99: f64,
+    pub mean: f64,
+    pub std_dev: f64,
+    /// Lower bound of the 95% confidence interval of the mean.
+    pub ci_low: f64,
+    /// Upper bound of the 95% confidence interval of the mean.
+    pub ci_high: f64,
+}
+
+impl BenchStats {
+    /// Compute statistics from measured samples. Returns [`None`] if there are no samples.
+    pub fn from_samples(samples: &[Duration], warmup: u64) -> Option<Self> {
+        #[allow(clippy::cast_precision_loss)]
+        let mut sorted: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+
+        if sorted.is_empty() {
+            return None;
+        }
+
+        sorted.sort_unstable_by(f64::total_cmp);
+
+        let q1 = quantile(&sorted, 0.25);
+        let q3 = quantile(&sorted, 0.75);
+        let iqr = q3 - q1;
+        let (fence_low, fence_high) = (q1 - TUKEY_FENCE * iqr, q3 + TUKEY_FENCE * iqr);
+
+        let inliers: Vec<f64> = sorted
+            .iter()
+            .copied()
+            .filter(|x| *x >= fence_low && *x <= fence_high)
+            .collect();
+
+        #[allow(clippy::cast_precision_loss)]
+        let n = inliers.len() as f64;
+        let mean = inliers.iter().sum::<f64>() / n;
+        let std_dev = if inliers.len() > 1 {
+            (inliers.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+        let margin = Z_95 * std_dev / n.sqrt();
+
+        Some(Self {
+            samples: sorted.len() as u64,
+            warmup,
+            outliers: (sorted.len() - inliers.len()) as u64,
+            min: sorted[0],
+            median: quantile(&sorted, 0.5),
+            p90: quantile(&sorted, 0.9),
+            p99: quantile(&sorted, 0.99),
+            mean,
+            std_dev,
+            ci_low: mean - margin,
+            ci_high: mean + margin,
+        })
+    }
+
+    /// Compare the mean against a `baseline` with Welch's t-test at the 95% level.
+    /// Outliers are excluded, as they are from the mean.
+    pub fn compare(&self, baseline: &BenchStats) -> Change {
+        let relative = (self.mean - baseline.mean) / baseline.mean;
+
+        #[allow(clippy::cast_precision_loss)]
+        let (n_1, n_2) = (
+            (self.samples - self.outliers).max(1) as f64,
+            (baseline.samples - baseline.outliers).max(1) as f64,
+        );
+        let (v_1, v_2) = (self.std_dev.powi(2) / n_1, baseline.std_dev.powi(2) / n_2);
+        let std_err = (v_1 + v_2).sqrt();
+
+        let is_significant = if std_err == 0.0 {
+            self.mean != baseline.mean
+        } else {
+            // Welch-Satterthwaite approximation of the degrees of freedom.
+            let df = (v_1 + v_2).powi(2)
+                / (v_1.powi(2) / (n_1 - 1.0).max(1.0) + v_2.powi(2) / (n_2 - 1.0).max(1.0));
+            ((self.mean - baseline.mean) / std_err).abs() > t_critical(df)
+        };
+
+        if !is_significant || relative.abs() < MIN_RELATIVE_CHANGE {
+            Change::Unchanged(relative)
+        } else if relative > 0.0 {
+            Change::Regression(relative)
+        } else {
+            Change::Improvement(relative)
+        }
+    }
+}
+
+/// Two-sided 95% critical value of Student's t-distribution with `df` degrees of freedom,
+/// via the Cornish-Fisher expansion around the normal distribution.
+fn t_critical(df: f64) -> f64 {
+    let z = Z_95;
+    z + (z.powi(3) + z) / (4.0 * df)
+        + (5.0 * z.powi(5) + 16.0 * z.powi(3) + 3.0 * z) / (96.0 * df.powi(2))
+}
+
+/// Linearly interpolated quantile `q` (0..=1) of a sorted, non-empty slice.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let pos = q * (sorted.len() - 1) as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (pos.floor() as usize, pos.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (pos - pos.floor())
+}
+
+/// Format nanoseconds the same way durations are formatted elsewhere in the runner.
+/// Durations below a microsecond keep two decimals if they fall between whole nanoseconds,
+/// which a `Duration` can't represent.
+pub fn format_nanos(nanos: f64) -> String {
+    let nanos = nanos.max(0.0);
+    if nanos < 1000.0 && nanos.fract() != 0.0 {
+        return format!("{nanos:.2}ns");
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos.round() as u64);
+    format!("{duration:.1?}")
+}
+
+impl Display for BenchStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {} · median {} · p90 {} · p99 {} · mean {} ± {} (95% CI {}..{}) · {} outliers",
+            format_nanos(self.min),
+            format_nanos(self.median),
+            format_nanos(self.p90),
+            format_nanos(self.p99),
+            format_nanos(self.mean),
+            format_nanos(self.std_dev),
+            format_nanos(self.ci_low),
+            format_nanos(self.ci_high),
+            self.outliers,
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+            map.insert("warmup".into(), JsonValue::Number(value.warmup as f64));
+            map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+        }
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("p90".into(), JsonValue::Number(value.p90));
+        map.insert("p99".into(), JsonValue::Number(value.p99));
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("std_dev".into(), JsonValue::Number(value.std_dev));
+        map.insert("ci_low".into(), JsonValue::Number(value.ci_low));
+        map.insert("ci_high".into(), JsonValue::Number(value.ci_high));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(BenchStats {
+            samples: number("samples")? as u64,
+            warmup: number("warmup")? as u64,
+            outliers: number("outliers")? as u64,
+            min: number("min")?,
+            median: number("median")?,
+            p90: number("p90")?,
+            p99: number("p99")?,
+            mean: number("mean")?,
+            std_dev: number("std_dev")?,
+            ci_low: number("ci_low")?,
+            ci_high: number("ci_high")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{t_critical, BenchStats, Change};
+
+    fn samples(nanos: &[u64]) -> Vec<Duration> {
+        nanos.iter().map(|n| Duration::from_nanos(*n)).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(BenchStats::from_samples(&[], 0), None);
+    }
+
+    #[test]
+    fn computes_order_statistics() {
+        let stats = BenchStats::from_samples(&samples(&[5, 1, 4, 2, 3]), 1).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.warmup, 1);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.median, 3.0);
+        assert_eq!(stats.mean, 3.0);
+        assert_eq!(stats.outliers, 0);
+        assert!((stats.p90 - 4.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn excludes_outliers_from_mean() {
+        let stats = BenchStats::from_samples(&samples(&[10, 10, 11, 10, 11, 10, 1000]), 0).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.median, 10.0);
+        assert!(stats.mean < 11.0);
+        assert!(stats.ci_low <= stats.mean && stats.mean <= stats.ci_high);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = BenchStats::from_samples(&samples(&[7, 8, 9]), 2).unwrap();
+        let json = JsonValue::from(&stats);
+        assert_eq!(BenchStats::try_from(&json).unwrap(), stats);
+    }
+
+    #[test]
+    fn approximates_t_critical_values() {
+        assert!((t_critical(10.0) - 2.228).abs() < 0.01);
+        assert!((t_critical(1000.0) - 1.962).abs() < 0.01);
+    }
+
+    #[test]
+    fn detects_significant_changes() {
+        let baseline =
+            BenchStats::from_samples(&samples(&[100, 101, 99, 100, 102, 98]), 0).unwrap();
+        let slower =
+            BenchStats::from_samples(&samples(&[120, 121, 119, 120, 122, 118]), 0).unwrap();
+        let noisy = BenchStats::from_samples(&samples(&[60, 140, 80, 150, 70, 130]), 0).unwrap();
+        let close = BenchStats::from_samples(&samples(&[102, 103, 101, 102, 104, 100]), 0).unwrap();
+
+        assert!(
+            matches!(slower.compare(&baseline), Change::Regression(x) if (x - 0.2).abs() < 1e-9)
+        );
+        assert!(matches!(baseline.compare(&slower), Change::Improvement(_)));
+        assert!(matches!(noisy.compare(&baseline), Change::Unchanged(_)));
+        assert!(matches!(close.compare(&baseline), Change::Unchanged(_)));
+    }
+}
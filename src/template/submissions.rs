@@ -0,0 +1,454 @@
+//! Ledger of submitted answers and the server's verdicts, used to refuse known-wrong answers.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::Error,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year};
+
+static SUBMISSIONS_FILE_NAME: &str = "submissions.json";
+
+/// Path of the submissions file of a year, e.g. `data/2023/submissions.json`.
+fn get_file_path(year: Year) -> String {
+    format!("{}/{SUBMISSIONS_FILE_NAME}", year.data_dir())
+}
+
+/// Verdict of the Advent of Code server for a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ServerVerdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited,
+    /// The response could not be interpreted, e.g. because the part was already solved.
+    Unknown,
+}
+
+impl ServerVerdict {
+    /// Interpret the response text of the Advent of Code server.
+    pub fn from_response(response: &str) -> Self {
+        let response = response.to_lowercase();
+
+        if response.contains("that's the right answer") {
+            ServerVerdict::Correct
+        } else if response.contains("too recently") {
+            ServerVerdict::RateLimited
+        } else if response.contains("too high") {
+            ServerVerdict::TooHigh
+        } else if response.contains("too low") {
+            ServerVerdict::TooLow
+        } else if response.contains("not the right answer") {
+            ServerVerdict::Wrong
+        } else {
+            ServerVerdict::Unknown
+        }
+    }
+
+    /// Returns `true` if the server rejected the answer itself, not just the request.
+    pub fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            ServerVerdict::TooHigh | ServerVerdict::TooLow | ServerVerdict::Wrong
+        )
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            ServerVerdict::Correct => "correct",
+            ServerVerdict::TooHigh => "too_high",
+            ServerVerdict::TooLow => "too_low",
+            ServerVerdict::Wrong => "wrong",
+            ServerVerdict::RateLimited => "rate_limited",
+            ServerVerdict::Unknown => "unknown",
+        }
+    }
+}
+
+impl FromStr for ServerVerdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(ServerVerdict::Correct),
+            "too_high" => Ok(ServerVerdict::TooHigh),
+            "too_low" => Ok(ServerVerdict::TooLow),
+            "wrong" => Ok(ServerVerdict::Wrong),
+            "rate_limited" => Ok(ServerVerdict::RateLimited),
+            "unknown" => Ok(ServerVerdict::Unknown),
+            s => Err(format!("Unknown submission verdict `{s}`.")),
+        }
+    }
+}
+
+impl Display for ServerVerdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ServerVerdict::Correct => "correct",
+            ServerVerdict::TooHigh => "too high",
+            ServerVerdict::TooLow => "too low",
+            ServerVerdict::Wrong => "wrong",
+            ServerVerdict::RateLimited => "rate-limited",
+            ServerVerdict::Unknown => "unknown",
+        })
+    }
+}
+
+/// Represents a single submitted answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub verdict: ServerVerdict,
+}
+
+/// Reason why an answer should not be submitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved { answer: String },
+    KnownWrong { verdict: ServerVerdict },
+    NotBelow { too_high: i128 },
+    NotAbove { too_low: i128 },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => {
+                write!(f, "this part was already solved with `{answer}`.")
+            }
+            Refusal::KnownWrong { verdict } => {
+                write!(f, "this answer was already submitted and was {verdict}.")
+            }
+            Refusal::NotBelow { too_high } => {
+                write!(f, "`{too_high}` was too high, the answer must be lower.")
+            }
+            Refusal::NotAbove { too_low } => {
+                write!(f, "`{too_low}` was too low, the answer must be higher.")
+            }
+        }
+    }
+}
+
+/// Ledger of all submitted answers.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions to the JSON file of a year.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        fs::create_dir_all(year.data_dir())?;
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(get_file_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from the JSON file of a year. If not present, returns an empty ledger.
+    pub fn read_from_file(year: Year) -> Self {
+        let s = fs::read_to_string(get_file_path(year))
+            .map_err(|x| x.to_string())
+            .and_then(Submissions::try_from);
+
+        match s {
+            Ok(submissions) => submissions,
+            Err(e) => {
+                eprintln!("{}", e);
+                Submissions::default()
+            }
+        }
+    }
+
+    /// Record a submission with the current time.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, verdict: ServerVerdict) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        self.data.push(Submission {
+            day,
+            part,
+            answer: answer.trim().to_string(),
+            timestamp,
+            verdict,
+        });
+    }
+
+    /// Check if `answer` is worth submitting given earlier feedback for the same part.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Refusal> {
+        let answer = answer.trim();
+        let previous = || {
+            self.data
+                .iter()
+                .filter(move |s| s.day == day && s.part == part)
+        };
+
+        if let Some(correct) = previous().find(|s| s.verdict == ServerVerdict::Correct) {
+            return Err(Refusal::AlreadySolved {
+                answer: correct.answer.clone(),
+            });
+        }
+
+        if let Some(wrong) = previous().find(|s| s.answer == answer && s.verdict.is_wrong()) {
+            return Err(Refusal::KnownWrong {
+                verdict: wrong.verdict,
+            });
+        }
+
+        // bounds only apply to numeric answers.
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let numeric = |verdict| {
+            previous()
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+
+        if let Some(too_high) = numeric(ServerVerdict::TooHigh).min() {
+            if value >= too_high {
+                return Err(Refusal::NotBelow { too_high });
+            }
+        }
+
+        if let Some(too_low) = numeric(ServerVerdict::TooLow).max() {
+            if value <= too_low {
+                return Err(Refusal::NotAbove { too_low });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>().copied())
+            .filter(|p| *p == 1.0 || *p == 2.0)
+            .map(|p| p as u8)
+            .ok_or("Expected submission.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .map(|t| t as u64)
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        Ok(Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            timestamp,
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Refusal, ServerVerdict, Submissions};
+    use crate::day;
+
+    fn get_mock_submissions() -> Submissions {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(1), 1, "100", ServerVerdict::TooHigh);
+        submissions.record(day!(1), 1, "20", ServerVerdict::TooLow);
+        submissions.record(day!(1), 1, "50", ServerVerdict::Wrong);
+        submissions.record(day!(1), 2, "abc", ServerVerdict::Wrong);
+        submissions.record(day!(2), 1, "7", ServerVerdict::Correct);
+        submissions
+    }
+
+    #[test]
+    fn parses_server_responses() {
+        let cases = [
+            (
+                "That's the right answer! You are one gold star closer.",
+                ServerVerdict::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high.",
+                ServerVerdict::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                ServerVerdict::TooLow,
+            ),
+            (
+                "That's not the right answer. If you're stuck, ...",
+                ServerVerdict::Wrong,
+            ),
+            (
+                "You gave an answer too recently; you have to wait",
+                ServerVerdict::RateLimited,
+            ),
+            (
+                "You don't seem to be solving the right level.",
+                ServerVerdict::Unknown,
+            ),
+        ];
+
+        for (response, verdict) in cases {
+            assert_eq!(ServerVerdict::from_response(response), verdict);
+        }
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(1), 1, "50"),
+            Err(Refusal::KnownWrong {
+                verdict: ServerVerdict::Wrong
+            })
+        );
+        assert_eq!(
+            submissions.check(day!(1), 2, " abc\n"),
+            Err(Refusal::KnownWrong {
+                verdict: ServerVerdict::Wrong
+            })
+        );
+    }
+
+    #[test]
+    fn refuses_answers_out_of_bounds() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(1), 1, "100"),
+            Err(Refusal::KnownWrong {
+                verdict: ServerVerdict::TooHigh
+            })
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "150"),
+            Err(Refusal::NotBelow { too_high: 100 })
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "3"),
+            Err(Refusal::NotAbove { too_low: 20 })
+        );
+        assert_eq!(submissions.check(day!(1), 1, "42"), Ok(()));
+        assert_eq!(submissions.check(day!(1), 2, "xyz"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(2), 1, "8"),
+            Err(Refusal::AlreadySolved { answer: "7".into() })
+        );
+        assert_eq!(submissions.check(day!(2), 2, "8"), Ok(()));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let submissions = get_mock_submissions();
+        let json = tinyjson::JsonValue::from(submissions.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Submissions::try_from(json).unwrap();
+        assert_eq!(parsed.data, submissions.data);
+    }
+}
@@ -0,0 +1,252 @@
+//! Module templates used to scaffold new solutions.
+//!
+//! Templates are looked up by name, first in the user templates directory (`templates/` or
+//! `AOC_TEMPLATES_DIR`) as `<name>.txt`, then among the built-in templates in `src/templates/`.
+//! A user template with the name of a built-in one replaces it, e.g. `templates/default.txt`.
+//!
+//! Templates can use the following placeholders:
+//!  - `%DAY_NUMBER%`: the day without padding, e.g. `5`.
+//!  - `%YEAR%`: the year, e.g. `2023`.
+//!  - `%TITLE%`: the puzzle title, e.g. `Day 5: If You Give A Seed A Fertilizer`.
+//!  - `%ANSWER_TYPE%`: the type of the answers, e.g. `u32`.
+//!  - `%PARSER_IMPORTS%`, `%PARSER%` and `%PARSER_TEST%`: the `use` declaration, the `parse`
+//!    function and its test, for the shape inferred from the input if it was downloaded.
+//!    On a line of their own, these placeholders remove the line if nothing was inferred.
+use std::{env, fmt::Display, fs, io, path::Path};
+
+use crate::template::input_shape::{self, ParserStub};
+use crate::template::PuzzleId;
+
+pub const DEFAULT_TEMPLATE: &str = "default";
+pub const DEFAULT_ANSWER_TYPE: &str = "u32";
+
+static TEMPLATES_DIR: &str = "templates";
+
+macro_rules! builtin_template {
+    ($name:literal) => {
+        (
+            $name,
+            include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/templates/",
+                $name,
+                ".txt"
+            )),
+        )
+    };
+}
+
+const BUILTIN_TEMPLATES: [(&str, &str); 5] = [
+    builtin_template!("default"),
+    builtin_template!("grid"),
+    builtin_template!("lines"),
+    builtin_template!("nom"),
+    builtin_template!("numbers"),
+];
+
+#[derive(Debug)]
+pub enum TemplateError {
+    NotFound(String),
+    IO(io::Error),
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::NotFound(name) => {
+                let builtin: Vec<&str> = BUILTIN_TEMPLATES.iter().map(|(name, _)| *name).collect();
+                write!(
+                    f,
+                    "template `{name}` not found in \"{}\" nor among the built-in templates: {}.",
+                    templates_dir(),
+                    builtin.join(", ")
+                )
+            }
+            TemplateError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for TemplateError {
+    fn from(e: io::Error) -> Self {
+        TemplateError::IO(e)
+    }
+}
+
+/// Values substituted for the placeholders of a template.
+#[derive(Debug, Clone)]
+pub struct Placeholders {
+    pub puzzle: PuzzleId,
+    pub title: String,
+    pub answer_type: String,
+    pub parser: Option<ParserStub>,
+}
+
+impl Placeholders {
+    /// Placeholders for a puzzle, taking the title from its description and the parser from the
+    /// shape of its input, if they were downloaded.
+    pub fn new(puzzle: PuzzleId, answer_type: Option<String>) -> Self {
+        let title = fs::read_to_string(puzzle.puzzle_path())
+            .ok()
+            .and_then(|markdown| read_title(&markdown))
+            .unwrap_or_else(|| format!("Day {}", puzzle.day.into_inner()));
+
+        let parser = fs::read_to_string(puzzle.input_path())
+            .ok()
+            .and_then(|input| input_shape::infer(&input))
+            .map(|shape| shape.parser_stub());
+
+        Self {
+            puzzle,
+            title,
+            answer_type: answer_type.unwrap_or_else(|| DEFAULT_ANSWER_TYPE.into()),
+            parser,
+        }
+    }
+}
+
+fn templates_dir() -> String {
+    env::var("AOC_TEMPLATES_DIR").unwrap_or_else(|_| TEMPLATES_DIR.into())
+}
+
+/// Read the title from a puzzle description, e.g. `## --- Day 1: Trebuchet?! ---`.
+fn read_title(markdown: &str) -> Option<String> {
+    markdown.lines().find_map(|line| {
+        let title = line
+            .trim()
+            .strip_prefix("## --- ")?
+            .strip_suffix(" ---")?
+            .trim();
+        title.starts_with("Day ").then(|| title.to_string())
+    })
+}
+
+/// Load a template by name from `dir`, falling back to the built-in templates.
+fn load_from(dir: &Path, name: &str) -> Result<String, TemplateError> {
+    match fs::read_to_string(dir.join(format!("{name}.txt"))) {
+        Ok(template) => return Ok(template),
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+        Err(_) => {}
+    }
+
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, template)| (*template).to_string())
+        .ok_or_else(|| TemplateError::NotFound(name.into()))
+}
+
+/// Load a template by name from the user templates directory or the built-in templates.
+pub fn load(name: &str) -> Result<String, TemplateError> {
+    load_from(Path::new(&templates_dir()), name)
+}
+
+/// Substitute a placeholder for a block of code, which is separated from the next line by a blank
+/// line. An empty block removes the line of the placeholder.
+fn replace_block(template: &str, placeholder: &str, block: Option<&str>) -> String {
+    let block = block.map(|block| format!("{block}\n")).unwrap_or_default();
+    template
+        .replace(&format!("{placeholder}\n"), &block)
+        .replace(placeholder, &block)
+}
+
+/// Substitute the placeholders of a template.
+pub fn render(template: &str, placeholders: &Placeholders) -> String {
+    let parser = placeholders.parser.as_ref();
+    let template = replace_block(
+        template,
+        "%PARSER_IMPORTS%",
+        parser.map(|p| p.imports.as_str()),
+    );
+    let template = replace_block(&template, "%PARSER_TEST%", parser.map(|p| p.test.as_str()));
+    let template = replace_block(&template, "%PARSER%", parser.map(|p| p.parser.as_str()));
+
+    template
+        .replace(
+            "%DAY_NUMBER%",
+            &placeholders.puzzle.day.into_inner().to_string(),
+        )
+        .replace("%YEAR%", &placeholders.puzzle.year.to_string())
+        .replace("%TITLE%", &placeholders.title)
+        .replace("%ANSWER_TYPE%", &placeholders.answer_type)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs};
+
+    use super::{load_from, read_title, render, Placeholders, TemplateError};
+    use crate::template::input_shape::ParserStub;
+    use crate::template::PuzzleId;
+    use crate::{day, year};
+
+    fn get_placeholders() -> Placeholders {
+        Placeholders {
+            puzzle: PuzzleId::new(year!(2023), day!(5)),
+            title: "Day 5: If You Give A Seed A Fertilizer".into(),
+            answer_type: "u64".into(),
+            parser: None,
+        }
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let template =
+            "// %TITLE%\n// %YEAR%\nsolution!(%DAY_NUMBER%);\nfn a() -> Option<%ANSWER_TYPE%>";
+        assert_eq!(
+            render(template, &get_placeholders()),
+            "// Day 5: If You Give A Seed A Fertilizer\n// 2023\nsolution!(5);\nfn a() -> Option<u64>"
+        );
+    }
+
+    #[test]
+    fn renders_parser_blocks() {
+        let template = "%PARSER_IMPORTS%\nsolution!();\n\n%PARSER%\nfn a() {}\n";
+        assert_eq!(
+            render(template, &get_placeholders()),
+            "solution!();\n\nfn a() {}\n"
+        );
+
+        let placeholders = Placeholders {
+            parser: Some(ParserStub {
+                shape: String::new(),
+                imports: "use nom::IResult;\n".into(),
+                parser: "fn parse() {}\n".into(),
+                test: String::new(),
+            }),
+            ..get_placeholders()
+        };
+        assert_eq!(
+            render(template, &placeholders),
+            "use nom::IResult;\n\nsolution!();\n\nfn parse() {}\n\nfn a() {}\n"
+        );
+    }
+
+    #[test]
+    fn reads_title() {
+        let markdown =
+            "## --- Day 1: Trebuchet?! ---\n\nSomething is wrong.\n\n## --- Part Two ---";
+        assert_eq!(read_title(markdown), Some("Day 1: Trebuchet?!".into()));
+        assert_eq!(read_title("no title"), None);
+    }
+
+    #[test]
+    fn prefers_user_templates() {
+        let dir = env::temp_dir().join(format!("aoc-templates-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("grid.txt"), "custom grid").unwrap();
+        fs::write(dir.join("mine.txt"), "custom").unwrap();
+
+        assert_eq!(load_from(&dir, "grid").unwrap(), "custom grid");
+        assert_eq!(load_from(&dir, "mine").unwrap(), "custom");
+        assert!(load_from(&dir, "nom").unwrap().contains("IResult"));
+        assert!(matches!(
+            load_from(&dir, "missing"),
+            Err(TemplateError::NotFound(_))
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,37 +1,102 @@
+//! Benchmark timings of every stage of each day, stored in `data/<year>/timings.json`.
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    history::{Run, RunEnvironment},
+    memory::MemoryStats,
+    stats::{format_nanos, BenchStats},
+    Day, Year,
+};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
+
+/// Path of the timings file of a year, e.g. `data/2023/timings.json`.
+fn get_file_path(year: Year) -> String {
+    format!("{}/{TIMINGS_FILE_NAME}", year.data_dir())
+}
+
+/// Represents the benchmark time of a single stage of a day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StageTiming {
+    /// Median execution time in nanoseconds, which can fall between whole nanoseconds.
+    pub nanos: f64,
+    /// Number of measured samples, `1` if the stage was executed once.
+    pub samples: u64,
+    pub stats: Option<BenchStats>,
+}
+
+impl StageTiming {
+    /// The execution time formatted for display, e.g. `74.1ms`.
+    pub fn formatted(&self) -> String {
+        format_nanos(self.nanos)
+    }
+}
 
 /// Represents benchmark times for a single day.
-#[derive(Clone, Debug)]
+/// `parse` is only present for solutions that declare a shared parse stage,
+/// `memory` only for days profiled with `cargo time --memory`.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
-    pub total_nanos: f64,
+    pub parse: Option<StageTiming>,
+    pub part_1: Option<StageTiming>,
+    pub part_2: Option<StageTiming>,
+    pub memory: Option<MemoryStats>,
+}
+
+impl Timing {
+    /// A timing without any benchmarked stage.
+    pub fn new(day: Day) -> Self {
+        Timing {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            memory: None,
+        }
+    }
+
+    /// The stages of this timing by name, e.g. `Part 1`.
+    pub fn stages(&self) -> [(&'static str, Option<&StageTiming>); 3] {
+        [
+            ("Parse", self.parse.as_ref()),
+            ("Part 1", self.part_1.as_ref()),
+            ("Part 2", self.part_2.as_ref()),
+        ]
+    }
+
+    /// Sum of the execution times of all stages in nanoseconds.
+    pub fn total_nanos(&self) -> f64 {
+        self.stages()
+            .into_iter()
+            .filter_map(|(_, stage)| stage)
+            .map(|stage| stage.nanos)
+            .sum()
+    }
 }
 
 /// Represents benchmark times for a set of days.
+/// `data` holds the latest timing of every day, `history` every recorded run in order.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
     pub data: Vec<Timing>,
+    pub history: Vec<Run>,
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings to the JSON file of a year.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        fs::create_dir_all(year.data_dir())?;
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(get_file_path(year))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate timings from the JSON file of a year. If not present, returns empty timings.
+    pub fn read_from_file(year: Year) -> Self {
+        let s = fs::read_to_string(get_file_path(year))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from);
 
@@ -45,11 +110,21 @@ impl Timings {
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// The memory stats of `self` are kept for days that `other` has no memory stats for.
+    /// The history of both is kept, runs of `other` after runs of `self`.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+            if timing.memory.is_none() {
+                timing.memory = self
+                    .data
+                    .iter()
+                    .find(|t| t.day == timing.day)
+                    .and_then(|t| t.memory);
+            }
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -58,13 +133,43 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by_key(|t| t.day);
+
+        let history = self.history.iter().chain(&new.history).cloned().collect();
+        Timings { data, history }
+    }
+
+    /// Record the timings of a run in the history and make them the latest timings.
+    pub fn record(&self, run: &Timings, environment: RunEnvironment) -> Self {
+        let mut timings = self.merge(run);
+        timings.history.push(Run {
+            environment,
+            data: run.data.clone(),
+        });
+        timings
+    }
+
+    /// Set the memory stats of a day, keeping its timings.
+    pub fn with_memory(&self, day: Day, memory: MemoryStats) -> Self {
+        let mut timings = self.clone();
+
+        match timings.data.iter_mut().find(|t| t.day == day) {
+            Some(timing) => timing.memory = Some(memory),
+            None => {
+                timings.data.push(Timing {
+                    memory: Some(memory),
+                    ..Timing::new(day)
+                });
+                timings.data.sort_unstable_by_key(|t| t.day);
+            }
+        }
+
+        timings
     }
 
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        self.data.iter().map(Timing::total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
     pub fn is_day_complete(&self, day: &Day) -> bool {
@@ -85,6 +190,11 @@ impl From<Timings> for JsonValue {
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
+        map.insert(
+            "history".into(),
+            JsonValue::Array(value.history.iter().map(JsonValue::from).collect()),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -95,9 +205,11 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
@@ -108,36 +220,137 @@ impl TryFrom<String> for Timings {
                 .iter()
                 .map(Timing::try_from)
                 .collect::<Result<_, _>>()?,
+            // the history is absent in timings stored by earlier versions.
+            history: match json.get("history") {
+                Some(v) => v
+                    .get::<Vec<JsonValue>>()
+                    .ok_or("expected `json.history` to be an array.")?
+                    .iter()
+                    .map(Run::try_from)
+                    .collect::<Result<_, _>>()?,
+                None => vec![],
+            },
         })
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
+impl From<&StageTiming> for JsonValue {
+    fn from(value: &StageTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for StageTiming {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stage timing to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stage timing.{key} to be a number."))
+        };
+
+        let stats = match json.get("stats") {
+            Some(v) if !v.is_null() => Some(BenchStats::try_from(v)?),
+            _ => None,
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(StageTiming {
+            nanos: number("nanos")?,
+            samples: number("samples")? as u64,
+            stats,
+        })
+    }
+}
+
+/// Parse a duration formatted by [`format_nanos`], e.g. `74.13ms`, into nanoseconds.
+fn parse_formatted_nanos(s: &str) -> Option<f64> {
+    let s = s.trim();
+    let unit_start = s.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (value, unit) = s.split_at(unit_start);
+    let value: f64 = value.parse().ok()?;
+
+    let factor = match unit {
+        "ns" => 1.0,
+        "µs" | "us" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+
+    Some(value * factor)
+}
+
+/// Read a stage of a timing stored by earlier versions, where the stage is a formatted string
+/// like `74.13ms` and its statistics are stored next to it in `<stage>_stats`.
+fn read_legacy_stage(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+    formatted: &str,
+) -> Result<StageTiming, String> {
+    let stats = match json.get(&format!("{key}_stats")) {
+        Some(v) if !v.is_null() => Some(BenchStats::try_from(v)?),
+        _ => None,
+    };
+
+    // the median of the statistics is more precise than the formatted time.
+    let nanos = match &stats {
+        Some(stats) => stats.median,
+        None => parse_formatted_nanos(formatted)
+            .ok_or(format!("Expected timing.{key} to be a duration."))?,
+    };
+
+    Ok(StageTiming {
+        nanos,
+        samples: stats.as_ref().map_or(1, |stats| stats.samples),
+        stats,
+    })
+}
+
 impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
-        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
-        map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        for (key, stage) in [
+            ("parse", &value.parse),
+            ("part_1", &value.part_1),
+            ("part_2", &value.part_2),
+        ] {
+            map.insert(
+                key.into(),
+                stage.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
 
         map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "memory".into(),
+            value
+                .memory
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
         );
 
         JsonValue::Object(map)
@@ -158,26 +371,31 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+        // the parse stage is absent in timings stored by earlier versions.
+        let read_stage = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::String(formatted)) => read_legacy_stage(json, key, formatted).map(Some),
+            Some(v @ JsonValue::Object(_)) => StageTiming::try_from(v).map(Some),
+            Some(_) => Err(format!("Expected timing.{key} to be null or an object.")),
+        };
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        if !json.contains_key("part_1") || !json.contains_key("part_2") {
+            return Err("Expected timing to have keys `part_1` and `part_2`.".into());
+        }
 
-        let total_nanos = json
-            .get("total_nanos")
-            .and_then(|v| v.get::<f64>().copied())
-            .ok_or("Expected timing.total_nanos to be a number.")?;
+        // memory stats are absent for days that were never profiled.
+        let memory = match json.get("memory") {
+            Some(v) if !v.is_null() => Some(MemoryStats::try_from(v)?),
+            _ => None,
+        };
 
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
-            total_nanos,
+            parse: read_stage("parse")?,
+            part_1: read_stage("part_1")?,
+            part_2: read_stage("part_2")?,
+            memory,
         })
     }
 }
@@ -188,30 +406,35 @@ impl TryFrom<&JsonValue> for Timing {
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{StageTiming, Timing, Timings};
+
+    pub fn stage(nanos: u64) -> Option<StageTiming> {
+        Some(StageTiming {
+            nanos: nanos as f64,
+            samples: 1,
+            stats: None,
+        })
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    part_1: stage(10_000_000),
+                    part_2: stage(20_000_000),
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    part_1: stage(30_000_000),
+                    part_2: stage(40_000_000),
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
-                    total_nanos: 4e+10,
+                    part_1: stage(40_000_000),
+                    ..Timing::new(day!(4))
                 },
             ],
+            history: vec![],
         }
     }
 
@@ -220,14 +443,40 @@ mod tests {
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "data": [{ "day": "01", "parse": null, "part_1": { "nanos": 1000000, "samples": 10, "stats": null }, "part_2": null }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            let part_1 = timing.part_1.as_ref().unwrap();
+            assert_eq!(part_1.nanos, 1_000_000.0);
+            assert_eq!(part_1.samples, 10);
+            assert_eq!(part_1.formatted(), "1.0ms");
             assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.total_nanos(), 1_000_000.0);
+        }
+
+        #[test]
+        fn migrates_formatted_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "74.13ms", "part_2": "2.6µs", "total_nanos": 74132600 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse, None);
+            assert_eq!(timing.part_1.as_ref().unwrap().nanos, 74_130_000.0);
+            assert_eq!(timing.part_1.as_ref().unwrap().samples, 1);
+            assert_eq!(timing.part_2.as_ref().unwrap().nanos, 2_600.0);
+        }
+
+        #[test]
+        fn migrates_formatted_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.0ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "samples": 10, "warmup": 1, "outliers": 2, "min": 900000, "median": 1000400, "p90": 1100000, "p99": 1200000, "mean": 1000000, "std_dev": 5000, "ci_low": 990000, "ci_high": 1010000 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let part_1 = timing.part_1.as_ref().unwrap();
+            assert_eq!(part_1.nanos, 1_000_400.0);
+            assert_eq!(part_1.samples, 10);
+            assert_eq!(part_1.stats.as_ref().unwrap().outliers, 2);
+            assert!(timing.part_2.is_none());
         }
 
         #[test]
@@ -250,10 +499,19 @@ mod tests {
             let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
             Timings::try_from(json).unwrap();
         }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_malformed_durations() {
+            let json =
+                r#"{ "data": [{ "day": "01", "part_1": "fast", "part_2": null }] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
     }
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::Timings;
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -273,9 +531,28 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn roundtrips_timings() {
+            let timings = get_mock_timings();
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            assert_eq!(Timings::try_from(json).unwrap().data, timings.data);
+        }
+    }
+
+    mod totals {
+        use super::get_mock_timings;
+
+        #[test]
+        fn sums_stages() {
+            let timings = get_mock_timings();
+            assert_eq!(timings.data[0].total_nanos(), 30_000_000.0);
+            assert_eq!(timings.total_millis(), 140.0);
+        }
     }
 
     mod is_day_complete {
+        use super::stage;
         use crate::{
             day,
             template::timings::{Timing, Timings},
@@ -285,11 +562,11 @@ mod tests {
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
+                    part_1: stage(1_000_000),
+                    part_2: stage(2_000_000),
+                    ..Timing::new(day!(1))
                 }],
+                history: vec![],
             };
 
             assert_eq!(timings.is_day_complete(&day!(1)), true);
@@ -299,11 +576,10 @@ mod tests {
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
-                    total_nanos: 1_000_000_000_f64,
+                    part_1: stage(1_000_000),
+                    ..Timing::new(day!(1))
                 }],
+                history: vec![],
             };
 
             assert_eq!(timings.is_day_complete(&day!(1)), false);
@@ -312,15 +588,69 @@ mod tests {
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
+                data: vec![Timing::new(day!(1))],
+                history: vec![],
+            };
+
+            assert_eq!(timings.is_day_complete(&day!(1)), false);
+        }
+    }
+
+    mod memory {
+        use super::{get_mock_timings, stage};
+        use crate::{
+            day,
+            template::{
+                memory::MemoryStats,
+                timings::{Timing, Timings},
+            },
+        };
+
+        const MEMORY: MemoryStats = MemoryStats {
+            peak_bytes: 232,
+            total_bytes: 276,
+            allocations: 3,
+        };
+
+        #[test]
+        fn sets_memory_of_timed_days() {
+            let timings = get_mock_timings().with_memory(day!(2), MEMORY);
+            assert_eq!(timings.data.len(), 3);
+            assert_eq!(timings.data[1].memory, Some(MEMORY));
+            assert_eq!(timings.data[1].total_nanos(), 70_000_000.0);
+        }
+
+        #[test]
+        fn adds_untimed_days() {
+            let timings = get_mock_timings().with_memory(day!(3), MEMORY);
+            assert_eq!(timings.data.len(), 4);
+            assert_eq!(timings.data[2].day, day!(3));
+            assert_eq!(timings.data[2].memory, Some(MEMORY));
+            assert_eq!(timings.data[2].part_1, None);
+        }
+
+        #[test]
+        fn keeps_memory_when_merging_timings() {
+            let timings = get_mock_timings().with_memory(day!(1), MEMORY);
+            let other = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
+                    part_1: stage(1),
+                    ..Timing::new(day!(1))
                 }],
+                history: vec![],
             };
+            let merged = timings.merge(&other);
+            assert_eq!(merged.data[0].part_1, stage(1));
+            assert_eq!(merged.data[0].memory, Some(MEMORY));
+        }
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+        #[test]
+        fn roundtrips_memory() {
+            let timings = get_mock_timings().with_memory(day!(4), MEMORY);
+            let json = tinyjson::JsonValue::from(timings.clone())
+                .stringify()
+                .unwrap();
+            assert_eq!(Timings::try_from(json).unwrap().data, timings.data);
         }
     }
 
@@ -336,12 +666,8 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(3))],
+                history: vec![],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -356,19 +682,15 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(2))],
+                history: vec![],
             };
             let merged = timings.merge(&other);
 
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].total_nanos(), 0.0);
             assert_eq!(merged.data[2].day, day!(4));
         }
 
@@ -0,0 +1,296 @@
+//! Re-runs a solution whenever its source or data changes.
+//!
+//! Changes are detected by polling the modification times of:
+//!  1. the solution, e.g. `src/bin/2023-05.rs`.
+//!  2. the shared library, i.e. every other file in `src/`, and `Cargo.toml`.
+//!  3. the day's data files, e.g. `data/2023/examples/05-2.txt` or `data/2023/inputs/05.txt`.
+//!
+//! Each run rebuilds the solution, runs it against its examples and then against the real input.
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{
+    report::{Stage, Status},
+    run_inputs::run_examples,
+    run_multi::child_commands,
+    Day, PuzzleId, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Modification times of the watched files.
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Answers of the real input by part.
+type Answers = BTreeMap<u8, String>;
+
+/// Returns `true` for data files of a day, e.g. `05.txt`, `05-2.txt` or `05.json`.
+fn is_day_file(day: Day, file_name: &str) -> bool {
+    let prefix = day.to_string();
+    file_name
+        .strip_prefix(&prefix)
+        .is_some_and(|rest| rest.starts_with('.') || rest.starts_with('-'))
+}
+
+/// Collect all files below `path`, skipping directories for which `skip` returns `true`.
+fn collect_files(path: &Path, skip: &dyn Fn(&Path) -> bool, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+
+    for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+        if path.is_dir() {
+            if !skip(&path) {
+                collect_files(&path, skip, files);
+            }
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+fn watched_files(puzzle: PuzzleId) -> Vec<PathBuf> {
+    let mut files = vec![
+        PathBuf::from(puzzle.bin_path()),
+        PathBuf::from("Cargo.toml"),
+    ];
+
+    // other solutions are not part of the shared library.
+    collect_files(Path::new("src"), &|dir| dir.ends_with("bin"), &mut files);
+
+    let data_dir = PathBuf::from(puzzle.year.data_dir());
+    for folder in ["examples", "inputs"] {
+        let Ok(entries) = fs::read_dir(data_dir.join(folder)) else {
+            continue;
+        };
+        entries
+            .filter_map(Result::ok)
+            .filter(|entry| is_day_file(puzzle.day, &entry.file_name().to_string_lossy()))
+            .for_each(|entry| files.push(entry.path()));
+    }
+
+    // additional inputs, e.g. `data/2023/inputs/05/alice.txt`.
+    collect_files(Path::new(&puzzle.inputs_dir()), &|_| false, &mut files);
+
+    files
+}
+
+fn snapshot(puzzle: PuzzleId) -> Snapshot {
+    watched_files(puzzle)
+        .into_iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect()
+}
+
+/// Files that were added, removed or modified between two snapshots.
+fn changed_files(previous: &Snapshot, current: &Snapshot) -> Vec<PathBuf> {
+    let modified = current
+        .iter()
+        .filter(|(path, modified)| previous.get(*path) != Some(*modified))
+        .map(|(path, _)| path.clone());
+    let removed = previous
+        .keys()
+        .filter(|path| !current.contains_key(*path))
+        .cloned();
+    modified.chain(removed).collect()
+}
+
+/// Describe how the answers changed since the previous run, e.g. `Part 1: 42 → 35`.
+fn answer_changes(previous: &Answers, current: &Answers) -> Vec<String> {
+    [1, 2]
+        .into_iter()
+        .filter_map(|part| {
+            let before = previous.get(&part);
+            let after = current.get(&part);
+            if before == after {
+                return None;
+            }
+            let format = |answer: Option<&String>| answer.map_or("✖".to_string(), Clone::clone);
+            Some(format!(
+                "Part {part}: {} → {}",
+                format(before),
+                format(after)
+            ))
+        })
+        .collect()
+}
+
+fn build(puzzle: PuzzleId, is_release: bool) -> io::Result<bool> {
+    let bin_name = puzzle.to_string();
+    let mut args = vec!["build", "--quiet", "--bin", &bin_name];
+    if is_release {
+        args.push("--release");
+    }
+
+    let status = Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()?;
+
+    Ok(status.success())
+}
+
+/// Run the examples and the real input once, returning the answers of the real input.
+/// Returns `None` if the solution could not be run.
+fn run_once(puzzle: PuzzleId, is_release: bool) -> Option<Answers> {
+    match build(puzzle, is_release) {
+        Ok(true) => {}
+        Ok(false) => {
+            eprintln!("Build failed.");
+            return None;
+        }
+        Err(e) => {
+            eprintln!("Failed to build {puzzle}: {e}");
+            return None;
+        }
+    }
+
+    println!("{ANSI_BOLD}Examples{ANSI_RESET}");
+    println!("--------");
+
+    match run_examples(puzzle, is_release) {
+        Ok(run) if run.inputs == 0 => println!("No examples listed."),
+        Ok(run) if run.failed.is_empty() => println!("\nAll {} example(s) passed.", run.inputs),
+        Ok(run) => println!(
+            "\n{} of {} example(s) failed.",
+            run.failed.len(),
+            run.inputs
+        ),
+        Err(e) => eprintln!("Failed to run examples: {e}"),
+    }
+
+    println!();
+    println!("{ANSI_BOLD}Input{ANSI_RESET}");
+    println!("-----");
+
+    let reports = match child_commands::run_solution(puzzle, None, None, false, is_release) {
+        Ok(reports) => reports,
+        Err(e) => {
+            eprintln!("Failed to run {puzzle}: {e}");
+            return None;
+        }
+    };
+
+    Some(
+        reports
+            .into_iter()
+            .filter(|r| r.status == Status::Solved)
+            .filter_map(|r| match r.stage {
+                Stage::Part(part) => Some((part, r.answer?)),
+                Stage::Parse => None,
+            })
+            .collect(),
+    )
+}
+
+/// Re-run a solution whenever its source or data changes. Runs until interrupted.
+pub fn watch(puzzle: PuzzleId, is_release: bool) -> ! {
+    let mut previous_snapshot = Snapshot::new();
+    let mut previous_answers: Option<Answers> = None;
+
+    loop {
+        let snapshot = snapshot(puzzle);
+        let changed = changed_files(&previous_snapshot, &snapshot);
+
+        if !changed.is_empty() {
+            print!("{ANSI_CLEAR_SCREEN}");
+            if !previous_snapshot.is_empty() {
+                let changed: Vec<String> = changed
+                    .iter()
+                    .map(|path| path.to_string_lossy().to_string())
+                    .collect();
+                println!("{ANSI_ITALIC}Changed: {}{ANSI_RESET}", changed.join(", "));
+            }
+            println!();
+
+            previous_snapshot = snapshot;
+
+            if let Some(answers) = run_once(puzzle, is_release) {
+                if let Some(previous) = &previous_answers {
+                    let changes = answer_changes(previous, &answers);
+                    println!();
+                    if changes.is_empty() {
+                        println!("{ANSI_ITALIC}Answers unchanged.{ANSI_RESET}");
+                    } else {
+                        println!("{ANSI_BOLD}Changed answers{ANSI_RESET}");
+                        changes.iter().for_each(|change| println!("{change}"));
+                    }
+                }
+                previous_answers = Some(answers);
+            }
+
+            println!();
+            println!(
+                "{ANSI_ITALIC}Watching {puzzle} for changes. Press Ctrl+C to stop.{ANSI_RESET}"
+            );
+            let _ = io::stdout().flush();
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        path::PathBuf,
+        time::{Duration, SystemTime},
+    };
+
+    use super::{answer_changes, changed_files, is_day_file, Answers, Snapshot};
+    use crate::day;
+
+    #[test]
+    fn matches_day_files() {
+        assert!(is_day_file(day!(5), "05.txt"));
+        assert!(is_day_file(day!(5), "05-2.txt"));
+        assert!(is_day_file(day!(5), "05.json"));
+        assert!(!is_day_file(day!(5), "15.txt"));
+        assert!(!is_day_file(day!(5), "050.txt"));
+    }
+
+    #[test]
+    fn detects_changed_files() {
+        let t = SystemTime::UNIX_EPOCH;
+        let previous = Snapshot::from([
+            (PathBuf::from("a"), t),
+            (PathBuf::from("b"), t),
+            (PathBuf::from("c"), t),
+        ]);
+        let current = Snapshot::from([
+            (PathBuf::from("a"), t),
+            (PathBuf::from("b"), t + Duration::from_secs(1)),
+            (PathBuf::from("d"), t),
+        ]);
+        assert_eq!(
+            changed_files(&previous, &current),
+            vec![PathBuf::from("b"), PathBuf::from("d"), PathBuf::from("c")]
+        );
+        assert!(changed_files(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn describes_answer_changes() {
+        let previous = Answers::from([(1, "42".to_string())]);
+        let current = Answers::from([(1, "35".to_string()), (2, "46".to_string())]);
+        assert_eq!(
+            answer_changes(&previous, &current),
+            vec!["Part 1: 42 → 35", "Part 2: ✖ → 46"]
+        );
+        assert!(answer_changes(&current, &current).is_empty());
+    }
+}
@@ -0,0 +1,183 @@
+//! Years of advent, used to namespace solutions and data.
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[cfg(feature = "today")]
+use chrono::{Datelike, Local};
+
+/// The first year of advent.
+const FIRST_YEAR: u16 = 2015;
+
+/// A valid year of advent (i.e. 2015 or later).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::Year;
+/// let year = Year::new(2023).unwrap();
+/// assert_eq!(year.to_string(), "2023")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(FIRST_YEAR..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    /// Extracts the year from a binary name like `2023-05` in a const context.
+    pub const fn __from_bin_name(name: &str) -> Self {
+        Self::parse_bin_name(name.as_bytes(), 0)
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    /// Extracts the year from the path of a solution like `src/bin/2023-05.rs` in a const context.
+    /// Unlike the binary name, the path is the same when a solution is linked into the main binary.
+    pub const fn __from_file_path(path: &str) -> Self {
+        let bytes = path.as_bytes();
+
+        let mut start = bytes.len();
+        while start > 0 && bytes[start - 1] != b'/' && bytes[start - 1] != b'\\' {
+            start -= 1;
+        }
+
+        Self::parse_bin_name(bytes, start)
+    }
+
+    const fn parse_bin_name(bytes: &[u8], start: usize) -> Self {
+        if bytes.len() < start + 5 || bytes[start + 4] != b'-' {
+            panic!("solution binaries must be named `<year>-<day>`, e.g. `2023-05`");
+        }
+
+        let mut year = 0;
+        let mut i = start;
+        while i < start + 4 {
+            if !bytes[i].is_ascii_digit() {
+                panic!("solution binaries must be named `<year>-<day>`, e.g. `2023-05`");
+            }
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        Self(year)
+    }
+
+    /// Returns the year configured via the `AOC_YEAR` environment variable, if valid.
+    pub fn from_env() -> Option<Self> {
+        std::env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the data folder of this year, e.g. `data/2023`.
+    pub fn data_dir(self) -> String {
+        format!("data/{self}")
+    }
+}
+
+#[cfg(feature = "today")]
+impl Year {
+    /// Returns the current year.
+    pub fn today() -> Option<Self> {
+        Self::new(u16::try_from(Local::now().year()).ok()?)
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of advent, i.e. 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015 && $year <= 9999,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting a year of advent, i.e. 2015 or later"
+            ),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2023".parse::<Year>().ok(), Some(Year(2023)));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("23".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn reads_year_from_bin_name() {
+        const YEAR: Year = Year::__from_bin_name("2022-08");
+        assert_eq!(YEAR, Year(2022));
+    }
+
+    #[test]
+    fn reads_year_from_file_path() {
+        const YEAR: Year = Year::__from_file_path("/root/aoc/src/bin/2022-08.rs");
+        assert_eq!(YEAR, Year(2022));
+        assert_eq!(Year::__from_file_path("src\\bin\\2021-01.rs"), Year(2021));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_legacy_bin_names() {
+        Year::__from_bin_name("08");
+    }
+}